@@ -1,48 +1,149 @@
-use aoc2024::aoc24::*;
-use aoc2024::util::fetch_input;
-use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
-use tokio::runtime::Runtime;
-
-macro_rules! bench_part {
-    ( $c:expr, $input:expr, $day:ident, $part:ident ) => {
-        $c.bench_function(
-            &format!("{} - {}", stringify!($day), stringify!($part)),
-            |b| {
-                b.iter_batched(|| $input.clone(), |i| $day::$part(i), BatchSize::LargeInput);
-            },
-        );
-    };
-}
+use std::{
+    env, fs,
+    hint::black_box,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use aoc2024::{
+    solution,
+    util::{
+        history::{current_commit, History, Timings, PHASES},
+        jsonl::now,
+        FsCache,
+    },
+};
+use criterion::{criterion_group, Criterion};
+use serde::Deserialize;
 
-macro_rules! day {
-    ( $day:ident ) => {
-        stringify!($day)[3..].parse::<u8>().unwrap()
-    };
+fn group_name(year: u16, day: u8) -> String {
+    format!("{year}-day{day:02}")
 }
 
-macro_rules! create_benches {
-    ( $( $day:ident ),* ) => {
-        $(
-            fn $day(c: &mut Criterion) {
-                let day = day!($day);
-                let input = Runtime::new().unwrap().block_on(fetch_input(2024, day));
+/// Benches parsing and each part separately, on inputs from the local input cache only.
+fn bench_solutions(c: &mut Criterion) {
+    let cache = FsCache::from_env();
+    for solution in solution::all() {
+        let (year, day) = (solution.year(), solution.day());
+        let input = match cache.read(year, day) {
+            Ok(input) => input.trim().to_string(),
+            Err(e) => {
+                eprintln!("skipping {year} day {day}: {e}");
+                continue;
+            }
+        };
 
-                bench_part!(c, input, $day, part1);
-                bench_part!(c, input, $day, part2);
+        let parsed = match solution.parse(&input) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                eprintln!("skipping {year} day {day}: {e}");
+                continue;
             }
-        )*
+        };
 
-        criterion_group!{
-            name = benches;
-            config = Criterion::default().sample_size(10);
-            targets = $( $day, )*
+        let mut group = c.benchmark_group(group_name(year, day));
+        group.bench_function("parse", |b| b.iter(|| solution.parse(black_box(&input))));
+        for part in [1, 2] {
+            group.bench_function(format!("part{part}"), |b| {
+                b.iter(|| solution.solve(part, black_box(&*parsed)))
+            });
         }
-    };
+        group.finish();
+    }
+}
+
+#[derive(Deserialize)]
+struct Estimates {
+    median: Estimate,
 }
 
-create_benches!(
-    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
-    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25
-);
+#[derive(Deserialize)]
+struct Estimate {
+    point_estimate: f64,
+}
 
-criterion_main!(benches);
+/// Where criterion writes its results, following the same environment variables it does.
+fn criterion_dir() -> PathBuf {
+    env::var_os("CRITERION_HOME").map_or_else(
+        || {
+            env::var_os("CARGO_TARGET_DIR")
+                .map_or_else(|| PathBuf::from("target"), PathBuf::from)
+                .join("criterion")
+        },
+        PathBuf::from,
+    )
+}
+
+/// The median criterion measured for `phase` of `group`, if it was measured after `since`.
+fn median(group: &str, phase: &str, since: SystemTime) -> Option<f64> {
+    let path = criterion_dir()
+        .join(group)
+        .join(phase)
+        .join("new")
+        .join("estimates.json");
+    if fs::metadata(&path).ok()?.modified().ok()? < since {
+        return None;
+    }
+    let estimates: Estimates = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    Some(estimates.median.point_estimate)
+}
+
+/// Collects the median of every phase of every day benched since `since`.
+fn medians(since: SystemTime) -> Vec<Timings> {
+    let commit = current_commit().unwrap_or_else(|| "unknown".to_string());
+    let timestamp = now();
+    solution::all()
+        .filter_map(|solution| {
+            let (year, day) = (solution.year(), solution.day());
+            let group = group_name(year, day);
+            let [parse, part1, part2] = PHASES.map(|phase| median(&group, phase, since));
+            (parse.is_some() || part1.is_some() || part2.is_some()).then(|| Timings {
+                commit: commit.clone(),
+                timestamp,
+                year,
+                day,
+                parse,
+                part1,
+                part2,
+            })
+        })
+        .collect()
+}
+
+fn summary(timings: &[Timings]) {
+    println!(
+        "\n{:<12}{:>12}{:>12}{:>12}",
+        "day", PHASES[0], PHASES[1], PHASES[2]
+    );
+    for t in timings {
+        let cells = PHASES.map(|phase| {
+            t.phase(phase).map_or("-".to_string(), |ns| {
+                format!("{:.2?}", Duration::from_secs_f64(ns / 1e9))
+            })
+        });
+        let group = group_name(t.year, t.day);
+        println!(
+            "{group:<12}{:>12}{:>12}{:>12}",
+            cells[0], cells[1], cells[2]
+        );
+    }
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = bench_solutions
+}
+
+fn main() {
+    let start = SystemTime::now();
+    benches();
+    Criterion::default().configure_from_args().final_summary();
+    let timings = medians(start);
+    summary(&timings);
+    let history = History::from_env();
+    match history.record(&timings) {
+        Ok(()) => println!("recorded {} days in the bench history", timings.len()),
+        Err(e) => eprintln!("couldn't record the bench history: {e}"),
+    }
+}
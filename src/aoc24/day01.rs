@@ -1,27 +1,55 @@
-use itertools::Itertools;
-
-pub fn parse(input: String) -> (Vec<u64>, Vec<u64>) {
-    input
-        .lines()
-        .map(|l| {
-            l.split_ascii_whitespace()
-                .map(|s| s.parse::<u64>().unwrap())
-                .collect_tuple::<(u64, u64)>()
-                .unwrap()
-        })
-        .unzip()
-}
+use crate::{
+    solution::{Example, Solution},
+    util::{Answer, ParseError, Parser},
+};
 
-pub fn part1(input: String) -> u64 {
-    let (mut a, mut b) = parse(input);
-    a.sort();
-    b.sort();
-    a.iter().zip(b).map(|(&a, b)| a.abs_diff(b)).sum()
-}
+const EXAMPLE_INPUT: &str = r"3   4
+4   3
+2   5
+1   3
+3   9
+3   3";
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 1;
+
+    type Config = ();
+    type Input = (Vec<u64>, Vec<u64>);
+
+    fn parse(input: &str, _: &()) -> Result<Self::Input, ParseError> {
+        let p = Parser::new(input);
+        let pairs = input
+            .lines()
+            .map(|l| {
+                let mut nums = l.split_ascii_whitespace();
+                let a = p.num::<u64>(p.next(&mut nums, l, "two numbers")?)?;
+                let b = p.num::<u64>(p.next(&mut nums, l, "a second number")?)?;
+                Ok((a, b))
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?;
+        Ok(pairs.into_iter().unzip())
+    }
+
+    fn part1((a, b): &Self::Input) -> Answer {
+        let mut a = a.clone();
+        let mut b = b.clone();
+        a.sort();
+        b.sort();
+        Answer::from(a.iter().zip(b).map(|(&a, b)| a.abs_diff(b)).sum::<u64>())
+    }
+
+    fn part2((a, b): &Self::Input) -> Answer {
+        Answer::from(
+            a.iter()
+                .map(|x| b.iter().filter(|&y| x == y).count() * (*x as usize))
+                .sum::<usize>() as u64,
+        )
+    }
 
-pub fn part2(input: String) -> u64 {
-    let (a, b) = parse(input);
-    a.iter()
-        .map(|x| b.iter().filter(|&y| x == y).count() * (*x as usize))
-        .sum::<usize>() as u64
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(11).part2(31)]
+    }
 }
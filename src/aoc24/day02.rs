@@ -1,42 +1,61 @@
 use itertools::Itertools;
 
-pub fn part1(input: String) -> u64 {
-    let lines = input
-        .lines()
-        .map(|line| {
-            line.split_ascii_whitespace()
-                .map(|s| s.parse::<u64>().unwrap())
-                .collect_vec()
-        })
-        .collect_vec();
-    lines
-        .iter()
-        .filter(|line| {
-            (line.iter().is_sorted() || line.iter().rev().is_sorted())
-                && line
-                    .windows(2)
-                    .all(|w| w[0].abs_diff(w[1]) >= 1 && w[0].abs_diff(w[1]) <= 3)
-        })
-        .count() as u64
-}
+use crate::{
+    solution::{Example, Solution},
+    util::{Answer, ParseError, Parser},
+};
+
+const EXAMPLE_INPUT: &str = r"7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9";
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 2;
+
+    type Config = ();
+    type Input = Vec<Vec<u64>>;
+
+    fn parse(input: &str, _: &()) -> Result<Self::Input, ParseError> {
+        let p = Parser::new(input);
+        input.lines().map(|line| p.words(line)).collect()
+    }
+
+    fn part1(lines: &Self::Input) -> Answer {
+        Answer::from(
+            lines
+                .iter()
+                .filter(|line| {
+                    (line.iter().is_sorted() || line.iter().rev().is_sorted())
+                        && line
+                            .windows(2)
+                            .all(|w| w[0].abs_diff(w[1]) >= 1 && w[0].abs_diff(w[1]) <= 3)
+                })
+                .count() as u64,
+        )
+    }
+
+    fn part2(lines: &Self::Input) -> Answer {
+        Answer::from(
+            lines
+                .iter()
+                .filter(|line| {
+                    line.iter().combinations(line.len() - 1).any(|c| {
+                        (c.iter().is_sorted() || c.iter().rev().is_sorted())
+                            && c.windows(2)
+                                .all(|w| w[0].abs_diff(*w[1]) >= 1 && w[0].abs_diff(*w[1]) <= 3)
+                    })
+                })
+                .count() as u64,
+        )
+    }
 
-pub fn part2(input: String) -> u64 {
-    let lines = input
-        .lines()
-        .map(|line| {
-            line.split_ascii_whitespace()
-                .map(|s| s.parse::<u64>().unwrap())
-                .collect_vec()
-        })
-        .collect_vec();
-    lines
-        .iter()
-        .filter(|line| {
-            line.iter().combinations(line.len() - 1).any(|c| {
-                (c.iter().is_sorted() || c.iter().rev().is_sorted())
-                    && c.windows(2)
-                        .all(|w| w[0].abs_diff(*w[1]) >= 1 && w[0].abs_diff(*w[1]) <= 3)
-            })
-        })
-        .count() as u64
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(2).part2(4)]
+    }
 }
@@ -1,7 +1,16 @@
-use std::sync::LazyLock;
+use crate::{
+    solution::{Example, Solution},
+    util::{Answer, ParseError},
+};
+
+const EXAMPLE_INPUT: &str =
+    r"xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+
+const EXAMPLE_INPUT_2: &str =
+    r"xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
 
 #[derive(Debug, Clone, Copy)]
-enum Instruction {
+pub enum Instruction {
     DO,
     DONT,
     MUL(u64, u64),
@@ -33,30 +42,51 @@ peg::parser! {
     }
 }
 
-pub fn part1(input: String) -> u64 {
-    if let Ok(tokens) = day3parser::day3(&input) {
-        return tokens.into_iter().fold(0, |acc, token| {
+pub struct Day03;
+
+impl Solution for Day03 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 3;
+
+    type Config = ();
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str, _: &()) -> Result<Self::Input, ParseError> {
+        day3parser::day3(input).map_err(|e| {
+            ParseError::new(
+                e.location.line,
+                e.location.column,
+                format!("expected {}", e.expected),
+            )
+        })
+    }
+
+    fn part1(tokens: &Self::Input) -> Answer {
+        Answer::from(tokens.iter().fold(0u64, |acc, token| {
             acc + match token {
                 Instruction::MUL(a, b) => a * b,
                 _ => 0,
             }
-        });
+        }))
+    }
+
+    fn part2(tokens: &Self::Input) -> Answer {
+        Answer::from(
+            tokens
+                .iter()
+                .fold((0, true), |(acc, enabled), token| match token {
+                    Instruction::DO => (acc, true),
+                    Instruction::DONT => (acc, false),
+                    Instruction::MUL(a, b) => (acc + if enabled { a * b } else { 0 }, enabled),
+                })
+                .0,
+        )
     }
-    0
-}
 
-pub fn part2(input: String) -> u64 {
-    if let Ok(tokens) = day3parser::day3(&input) {
-        return tokens
-            .into_iter()
-            .fold((0, true), |(acc, enabled), token| match token {
-                Instruction::DO => (acc, true),
-                Instruction::DONT => (acc, false),
-                Instruction::MUL(a, b) => {
-                    (acc + enabled.then(|| a * b).unwrap_or_default(), enabled)
-                }
-            })
-            .0;
+    fn examples() -> Vec<Example> {
+        vec![
+            Example::new(EXAMPLE_INPUT).part1(161),
+            Example::new(EXAMPLE_INPUT_2).part2(48),
+        ]
     }
-    0
 }
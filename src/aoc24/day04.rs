@@ -1,101 +1,82 @@
+use exhaust::Exhaust;
 use itertools::Itertools;
 
-pub fn part1(input: String) -> u64 {
-    let grid = input
-        .trim()
-        .lines()
-        .map(|l| l.chars().collect_vec())
-        .collect_vec();
-    let xmas = ['X', 'M', 'A', 'S'];
-    let mut res = 0;
-    (0..grid.len()).for_each(|x| {
-        (0..grid[x].len()).for_each(|y| {
-            if y + 3 < grid[x].len() && (grid[x][y..=y + 3] == xmas) {
-                res += 1;
-            }
-            if y >= 3 && ([grid[x][y], grid[x][y - 1], grid[x][y - 2], grid[x][y - 3]] == xmas) {
-                res += 1;
-            }
-            if x + 3 < grid.len()
-                && ([grid[x][y], grid[x + 1][y], grid[x + 2][y], grid[x + 3][y]] == xmas)
-            {
-                res += 1;
-            }
-            if x >= 3 && ([grid[x][y], grid[x - 1][y], grid[x - 2][y], grid[x - 3][y]] == xmas) {
-                res += 1;
-            }
-            if x + 3 < grid.len()
-                && y + 3 < grid[x].len()
-                && ([
-                    grid[x][y],
-                    grid[x + 1][y + 1],
-                    grid[x + 2][y + 2],
-                    grid[x + 3][y + 3],
-                ] == xmas)
-            {
-                res += 1;
-            }
-            if x >= 3
-                && y >= 3
-                && ([
-                    grid[x][y],
-                    grid[x - 1][y - 1],
-                    grid[x - 2][y - 2],
-                    grid[x - 3][y - 3],
-                ] == xmas)
-            {
-                res += 1;
-            }
-            if x + 3 < grid.len()
-                && y >= 3
-                && ([
-                    grid[x][y],
-                    grid[x + 1][y - 1],
-                    grid[x + 2][y - 2],
-                    grid[x + 3][y - 3],
-                ] == xmas)
-            {
-                res += 1;
-            }
-            if x >= 3
-                && y + 3 < grid[x].len()
-                && ([
-                    grid[x][y],
-                    grid[x - 1][y + 1],
-                    grid[x - 2][y + 2],
-                    grid[x - 3][y + 3],
-                ] == xmas)
-            {
-                res += 1;
-            }
-        });
-    });
-    res
+use crate::{
+    solution::{Example, Solution},
+    util::{Answer, Coordinate, Direction8, ParseError, Parser},
+};
+
+const EXAMPLE_INPUT: &str = r"MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+
+/// Whether `word` reads out from `start` one step at a time towards `dir`.
+fn spells(grid: &[Vec<char>], start: Coordinate, dir: Direction8, word: &str) -> bool {
+    let mut pos = Some(start);
+    word.chars().all(|c| {
+        let Some(p) = pos else {
+            return false;
+        };
+        pos = p.checked_add(dir.to_delta());
+        grid.get(p.row()).and_then(|row| row.get(p.col())) == Some(&c)
+    })
 }
 
-pub fn part2(input: String) -> u64 {
-    let grid = input
-        .trim()
-        .lines()
-        .map(|l| l.trim().chars().collect_vec())
-        .collect_vec();
-    let mut res = 0;
-    (0..grid.len() - 2).for_each(|x| {
-        (0..grid[x].len() - 2).for_each(|y| {
-            if grid[x + 1][y + 1] == 'A' {
-                // conor reference?
-                let mut corners = [
-                    grid[x][y],
-                    grid[x + 2][y],
-                    grid[x][y + 2],
-                    grid[x + 2][y + 2],
-                ];
-                corners.sort();
-                if corners == ['M', 'M', 'S', 'S'] && grid[x][y] != grid[x + 2][y + 2] {
-                    res += 1;
+pub struct Day04;
+
+impl Solution for Day04 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 4;
+
+    type Config = ();
+    type Input = Vec<Vec<char>>;
+
+    fn parse(input: &str, _: &()) -> Result<Self::Input, ParseError> {
+        let p = Parser::new(input);
+        p.only(input, b"XMAS", "X, M, A or S")?;
+        p.grid(input)?;
+        Ok(input.lines().map(|l| l.chars().collect_vec()).collect_vec())
+    }
+
+    fn part1(grid: &Self::Input) -> Answer {
+        let res = (0..grid.len())
+            .flat_map(|x| (0..grid[x].len()).map(move |y| Coordinate::from((x, y))))
+            .flat_map(|start| Direction8::exhaust().map(move |dir| (start, dir)))
+            .filter(|&(start, dir)| spells(grid, start, dir, "XMAS"))
+            .count();
+        Answer::from(res as u64)
+    }
+
+    fn part2(grid: &Self::Input) -> Answer {
+        let mut res: u64 = 0;
+        (0..grid.len().saturating_sub(2)).for_each(|x| {
+            (0..grid[x].len().saturating_sub(2)).for_each(|y| {
+                if grid[x + 1][y + 1] == 'A' {
+                    // conor reference?
+                    let mut corners = [
+                        grid[x][y],
+                        grid[x + 2][y],
+                        grid[x][y + 2],
+                        grid[x + 2][y + 2],
+                    ];
+                    corners.sort();
+                    if corners == ['M', 'M', 'S', 'S'] && grid[x][y] != grid[x + 2][y + 2] {
+                        res += 1;
+                    }
                 }
-            }
-        })
-    });
-    res
+            })
+        });
+        Answer::from(res)
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(18).part2(9)]
+    }
 }
@@ -2,9 +2,41 @@ use std::{cmp::Ordering, collections::HashSet};
 
 use itertools::Itertools;
 
-use crate::util::{fetch_input, submit};
+use crate::{
+    solution::{Example, Solution},
+    util::{Answer, ParseError, Parser},
+};
 
-fn validate(update: &Vec<u64>, rules: &HashSet<(u64, u64)>) -> bool {
+const EXAMPLE_INPUT: &str = r"47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47";
+
+fn validate(update: &[u64], rules: &HashSet<(u64, u64)>) -> bool {
     let mut combs = update.iter().combinations(2);
     combs.all(|c| {
         let &a = c[0];
@@ -13,92 +45,68 @@ fn validate(update: &Vec<u64>, rules: &HashSet<(u64, u64)>) -> bool {
     })
 }
 
-pub fn parse(input: &str) -> (Vec<(u64, u64)>, Vec<Vec<u64>>) {
-    let mut rules = vec![];
-    let mut updates = vec![];
-    let mut parsing_rules = true;
-    for line in input.lines() {
-        if line.len() == 0 {
-            parsing_rules = false;
-            continue;
-        }
-        if parsing_rules {
-            rules.push(
-                line.split("|")
-                    .map(|s| s.parse::<u64>().unwrap())
-                    .collect_tuple()
-                    .unwrap(),
-            );
-        } else {
-            updates.push(
-                line.split(",")
-                    .map(|s| s.parse::<u64>().unwrap())
-                    .collect_vec(),
-            );
-        }
-    }
-    (rules, updates)
-}
+pub struct Day05;
 
-fn parse2(input: &str) -> (Vec<Vec<u64>>, Vec<Vec<u64>>, HashSet<(u64, u64)>) {
-    let mut rules = HashSet::new();
-    let mut valid_updates = vec![];
-    let mut invalid_updates = vec![];
-    let mut parsing_rules = true;
-    for line in input.lines() {
-        if line.trim().len() == 0 {
-            parsing_rules = false;
-            continue;
-        }
-        if parsing_rules {
-            rules.insert(
-                line.trim()
-                    .split("|")
-                    .map(|s| s.parse::<u64>().unwrap())
-                    .collect_tuple()
-                    .unwrap(),
-            );
-        } else {
-            let update = line
-                .trim()
-                .split(",")
-                .map(|s| s.parse::<u64>().unwrap())
-                .collect_vec();
-            if validate(&update, &rules) {
-                valid_updates.push(update);
+impl Solution for Day05 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 5;
+
+    type Config = ();
+    type Input = (HashSet<(u64, u64)>, Vec<Vec<u64>>);
+
+    fn parse(input: &str, _: &()) -> Result<Self::Input, ParseError> {
+        let p = Parser::new(input);
+        let mut rules = HashSet::new();
+        let mut updates = vec![];
+        let mut parsing_rules = true;
+        for line in input.lines() {
+            if line.trim().is_empty() {
+                parsing_rules = false;
+                continue;
+            }
+            if parsing_rules {
+                rules.insert(p.pair(line.trim(), "|")?);
             } else {
-                invalid_updates.push(update);
+                updates.push(p.nums(line.trim(), ",")?);
             }
         }
+        Ok((rules, updates))
     }
-    (valid_updates, invalid_updates, rules)
-}
 
-pub fn part1(input: String) -> u64 {
-    let (valid, _, _) = parse2(&input.trim());
-    let res: u64 = valid.iter().map(|update| update[update.len() / 2]).sum();
-    res
-}
+    fn part1((rules, updates): &Self::Input) -> Answer {
+        let res: u64 = updates
+            .iter()
+            .filter(|update| validate(update, rules))
+            .map(|update| update[update.len() / 2])
+            .sum();
+        Answer::from(res)
+    }
+
+    fn part2((rules, updates): &Self::Input) -> Answer {
+        let res: u64 = updates
+            .iter()
+            .filter(|update| !validate(update, rules))
+            .map(|update| {
+                let len = update.len();
+                update
+                    .iter()
+                    .copied()
+                    .sorted_by(|&a, &b| {
+                        if rules.contains(&(a, b)) {
+                            return Ordering::Less;
+                        } else if rules.contains(&(b, a)) {
+                            return Ordering::Greater;
+                        }
+                        Ordering::Equal
+                    })
+                    .nth(len / 2)
+                    .unwrap()
+            })
+            .sum();
+        Answer::from(res)
+    }
 
-pub fn part2(input: String) -> u64 {
-    let (_, invalid, rules) = parse2(&input.trim());
-    let res: u64 = invalid
-        .into_iter()
-        .map(|update| {
-            let len = update.len();
-            update
-                .into_iter()
-                .sorted_by(|&a, &b| {
-                    if rules.contains(&(a, b)) {
-                        return Ordering::Less;
-                    } else if rules.contains(&(b, a)) {
-                        return Ordering::Greater;
-                    }
-                    Ordering::Equal
-                })
-                .nth(len / 2)
-                .unwrap()
-        })
-        .sum();
-    res
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(143).part2(123)]
+    }
 }
@@ -1,7 +1,11 @@
-use std::{fmt::Display, sync::LazyLock};
+use std::fmt::Display;
 
 use itertools::Itertools;
-use regex::Regex;
+
+use crate::{
+    solution::{Example, Solution},
+    util::{Answer, ParseError, Parser},
+};
 
 #[derive(Debug, Clone, Copy)]
 enum Operator {
@@ -44,86 +48,34 @@ const EXAMPLE_INPUT: &str = r"190: 10 19
 21037: 9 7 18 13
 292: 11 6 16 20";
 
-static LINE_REGEX: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"(\d+): ((?:\d+(?:\s+)?)+)").unwrap());
+pub struct Day07;
 
-pub fn parse(input: &str) -> Vec<(u64, Vec<u64>)> {
-    input
-        .lines()
-        .map(|l| {
-            let mut captures_iter = LINE_REGEX.captures_iter(l);
-            let capture = captures_iter.next().unwrap();
-            let goal = capture.get(1).unwrap().as_str().parse::<u64>().unwrap();
-            let nums = capture
-                .get(2)
-                .unwrap()
-                .as_str()
-                .split_ascii_whitespace()
-                .map(|s| s.parse::<u64>().unwrap())
-                .collect_vec();
-            (goal, nums)
-        })
-        .collect_vec()
-}
+impl Solution for Day07 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 7;
 
-pub fn part1(input: String) -> u64 {
-    let v = parse(&input);
-    let res: usize = v
-        .into_iter()
-        .map(|(goal, nums)| {
-            let mut acc_vals = Vec::new();
-            for i in 1..nums.len() {
-                if i == 1 {
-                    [Operator::ADD, Operator::MUL].into_iter().for_each(|op| {
-                        let val = op.apply(nums[0], nums[1]);
-                        if i == nums.len() - 1 {
-                            if val == goal {
-                                acc_vals.push(val);
-                            }
-                        } else if val <= goal {
-                            acc_vals.push(val);
-                        }
-                    });
-                } else {
-                    acc_vals = acc_vals
-                        .into_iter()
-                        .flat_map(|acc| {
-                            let mut res = Vec::new();
-                            for op in [Operator::ADD, Operator::MUL] {
-                                let val = op.apply(acc, nums[i]);
-                                if i == nums.len() - 1 {
-                                    if val == goal {
-                                        res.push(val);
-                                    }
-                                } else if val <= goal {
-                                    res.push(val);
-                                }
-                            }
-                            res
-                        })
-                        .collect_vec()
-                }
-            }
-            if acc_vals.len() > 0 {
-                return goal as usize;
-            }
-            0
-        })
-        .sum();
-    res as u64
-}
+    type Config = ();
+    type Input = Vec<(u64, Vec<u64>)>;
+
+    fn parse(input: &str, _: &()) -> Result<Self::Input, ParseError> {
+        let p = Parser::new(input);
+        input
+            .lines()
+            .map(|l| {
+                let (goal, nums) = p.split_once(l, ": ")?;
+                Ok((p.num(goal)?, p.words(nums)?))
+            })
+            .collect()
+    }
 
-pub fn part2(input: String) -> u64 {
-    let v = parse(&input);
-    let res: usize = v
-        .into_iter()
-        .map(|(goal, nums)| {
-            let mut acc_vals = Vec::new();
-            for i in 1..nums.len() {
-                if i == 1 {
-                    [Operator::ADD, Operator::MUL, Operator::CAT]
-                        .into_iter()
-                        .for_each(|op| {
+    fn part1(equations: &Self::Input) -> Answer {
+        let res: usize = equations
+            .iter()
+            .map(|&(goal, ref nums)| {
+                let mut acc_vals = Vec::new();
+                for i in 1..nums.len() {
+                    if i == 1 {
+                        [Operator::ADD, Operator::MUL].into_iter().for_each(|op| {
                             let val = op.apply(nums[0], nums[1]);
                             if i == nums.len() - 1 {
                                 if val == goal {
@@ -133,31 +85,84 @@ pub fn part2(input: String) -> u64 {
                                 acc_vals.push(val);
                             }
                         });
-                } else {
-                    acc_vals = acc_vals
-                        .into_iter()
-                        .flat_map(|acc| {
-                            let mut res = Vec::new();
-                            for op in [Operator::ADD, Operator::MUL, Operator::CAT] {
-                                let val = op.apply(acc, nums[i]);
+                    } else {
+                        acc_vals = acc_vals
+                            .into_iter()
+                            .flat_map(|acc| {
+                                let mut res = Vec::new();
+                                for op in [Operator::ADD, Operator::MUL] {
+                                    let val = op.apply(acc, nums[i]);
+                                    if i == nums.len() - 1 {
+                                        if val == goal {
+                                            res.push(val);
+                                        }
+                                    } else if val <= goal {
+                                        res.push(val);
+                                    }
+                                }
+                                res
+                            })
+                            .collect_vec()
+                    }
+                }
+                if !acc_vals.is_empty() {
+                    return goal as usize;
+                }
+                0
+            })
+            .sum();
+        Answer::from(res as u64)
+    }
+
+    fn part2(equations: &Self::Input) -> Answer {
+        let res: usize = equations
+            .iter()
+            .map(|&(goal, ref nums)| {
+                let mut acc_vals = Vec::new();
+                for i in 1..nums.len() {
+                    if i == 1 {
+                        [Operator::ADD, Operator::MUL, Operator::CAT]
+                            .into_iter()
+                            .for_each(|op| {
+                                let val = op.apply(nums[0], nums[1]);
                                 if i == nums.len() - 1 {
                                     if val == goal {
-                                        res.push(val);
+                                        acc_vals.push(val);
                                     }
                                 } else if val <= goal {
-                                    res.push(val);
+                                    acc_vals.push(val);
                                 }
-                            }
-                            res
-                        })
-                        .collect_vec()
+                            });
+                    } else {
+                        acc_vals = acc_vals
+                            .into_iter()
+                            .flat_map(|acc| {
+                                let mut res = Vec::new();
+                                for op in [Operator::ADD, Operator::MUL, Operator::CAT] {
+                                    let val = op.apply(acc, nums[i]);
+                                    if i == nums.len() - 1 {
+                                        if val == goal {
+                                            res.push(val);
+                                        }
+                                    } else if val <= goal {
+                                        res.push(val);
+                                    }
+                                }
+                                res
+                            })
+                            .collect_vec()
+                    }
                 }
-            }
-            if acc_vals.len() > 0 {
-                return goal as usize;
-            }
-            0
-        })
-        .sum();
-    res as u64
+                if !acc_vals.is_empty() {
+                    return goal as usize;
+                }
+                0
+            })
+            .sum();
+        Answer::from(res as u64)
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(3749).part2(11387)]
+    }
 }
@@ -3,7 +3,10 @@ use std::collections::HashSet;
 use grid::Grid;
 use itertools::Itertools;
 
-use crate::util::{fetch_input, submit};
+use crate::{
+    solution::{Example, Solution},
+    util::{Answer, Coordinate, IsValidIndex, ParseError, Parser},
+};
 
 const EXAMPLE_INPUT: &str = "............
 ........0...
@@ -18,95 +21,78 @@ const EXAMPLE_INPUT: &str = "............
 ............
 ............";
 
-fn antinode_locs(
-    a: (usize, usize),
-    b: (usize, usize),
-    rows: usize,
-    cols: usize,
-    max_mul: usize,
-) -> Vec<(usize, usize)> {
+fn antinode_locs(a: Coordinate, b: Coordinate, g: &Grid<char>, max_mul: isize) -> Vec<Coordinate> {
     let mut locs = Vec::new();
 
-    [1, -1].into_iter().for_each(|d| {
-        let mut mul = 1;
-        while mul <= max_mul {
-            let diff = (
-                ((a.0 as isize) - (b.0 as isize)) * d * mul as isize,
-                ((a.1 as isize) - (b.1 as isize)) * d * mul as isize,
-            );
-            let mut c = ((a.0 as isize) + diff.0, (a.1 as isize) + diff.1);
-            if (c.0 as usize, c.1 as usize) == b {
-                c = ((b.0 as isize) + diff.0, (b.1 as isize) + diff.1);
+    for d in [1, -1] {
+        for mul in 1..=max_mul {
+            let diff = (a - b) * (d * mul);
+            let mut c = a.checked_add(diff);
+            if c == Some(b) {
+                c = b.checked_add(diff);
             }
-            if c.0 >= 0 && (c.0 as usize) < rows && c.1 >= 0 && (c.1 as usize) < cols {
-                locs.push((c.0 as usize, c.1 as usize));
-                mul += 1;
-            } else {
-                break;
+            match c.filter(|&c| g.is_valid_index(c)) {
+                Some(c) => locs.push(c),
+                None => break,
             }
         }
-    });
-
+    }
     locs
 }
 
-pub fn part1(input: String) -> u64 {
-    let g = {
-        Grid::from_vec(
-            input.lines().flat_map(|l| l.chars()).collect_vec(),
-            input.find("\n").unwrap(),
-        )
-    };
-    let antenna_locs = g
-        .indexed_iter()
-        .filter_map(|(i, c)| c.is_ascii_alphanumeric().then_some(i))
-        .collect_vec();
+pub struct Day08;
 
-    let rows = g.rows();
-    let cols = g.cols();
-    let antinode_locs = antenna_locs
-        .iter()
-        .cartesian_product(antenna_locs.iter())
-        .filter_map(|(&a, &b)| {
-            (a != b && *g.get(a.0, a.1).unwrap() == *g.get(b.0, b.1).unwrap())
-                .then(|| antinode_locs(a, b, rows, cols, 1))
-        })
-        .flatten()
-        .collect::<HashSet<_>>();
-    let res = antinode_locs.len();
-    res as u64
-}
+impl Solution for Day08 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 8;
+
+    type Config = ();
+    type Input = Grid<char>;
+
+    fn parse(input: &str, _: &()) -> Result<Self::Input, ParseError> {
+        let grid = Parser::new(input).grid(input)?;
+        Ok(Grid::from_vec(
+            grid.iter().map(|&b| char::from(b)).collect_vec(),
+            grid.cols(),
+        ))
+    }
+
+    fn part1(g: &Self::Input) -> Answer {
+        let antenna_locs = g
+            .indexed_iter()
+            .filter_map(|(i, c)| c.is_ascii_alphanumeric().then_some(Coordinate::from(i)))
+            .collect_vec();
+
+        let antinode_locs = antenna_locs
+            .iter()
+            .cartesian_product(antenna_locs.iter())
+            .filter(|&(&a, &b)| a != b && g[a] == g[b])
+            .flat_map(|(&a, &b)| antinode_locs(a, b, g, 1))
+            .collect::<HashSet<_>>();
+        let res = antinode_locs.len();
+        Answer::from(res as u64)
+    }
+
+    fn part2(g: &Self::Input) -> Answer {
+        let antenna_locs = g
+            .indexed_iter()
+            .filter_map(|(i, c)| c.is_ascii_alphanumeric().then_some(Coordinate::from(i)))
+            .collect_vec();
 
-pub fn part2(input: String) -> u64 {
-    // let input = Grid::from_vec(
-    //     EXAMPLE_INPUT.lines().flat_map(|l| l.chars()).collect_vec(),
-    //     EXAMPLE_INPUT.find("\n").unwrap(),
-    // );
-    let g = {
-        Grid::from_vec(
-            input.lines().flat_map(|l| l.chars()).collect_vec(),
-            input.find("\n").unwrap(),
-        )
-    };
-    let antenna_locs = g
-        .indexed_iter()
-        .filter_map(|(i, c)| c.is_ascii_alphanumeric().then_some(i))
-        .collect_vec();
+        let mut antinode_locs = antenna_locs
+            .iter()
+            .cartesian_product(antenna_locs.iter())
+            .filter(|&(&a, &b)| a != b && g[a] == g[b])
+            .flat_map(|(&a, &b)| antinode_locs(a, b, g, 50))
+            .collect::<HashSet<_>>();
+        antenna_locs.iter().copied().for_each(|loc| {
+            antinode_locs.insert(loc);
+        });
+        let res = antinode_locs.len();
+        Answer::from(res as u64)
+    }
 
-    let rows = g.rows();
-    let cols = g.cols();
-    let mut antinode_locs = antenna_locs
-        .iter()
-        .cartesian_product(antenna_locs.iter())
-        .filter_map(|(&a, &b)| {
-            (a != b && *g.get(a.0, a.1).unwrap() == *g.get(b.0, b.1).unwrap())
-                .then(|| antinode_locs(a, b, rows, cols, 50))
-        })
-        .flatten()
-        .collect::<HashSet<_>>();
-    antenna_locs.iter().copied().for_each(|loc| {
-        antinode_locs.insert(loc);
-    });
-    let res = antinode_locs.len();
-    res as u64
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(14).part2(34)]
+    }
 }
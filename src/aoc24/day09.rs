@@ -2,6 +2,13 @@ use std::{collections::BTreeMap, fmt::Display};
 
 use itertools::Itertools;
 
+use crate::{
+    solution::{Example, Solution},
+    util::{Answer, ParseError, Parser},
+};
+
+const EXAMPLE_INPUT: &str = r"2333133121414131402";
+
 type FileId = u64;
 type FileSize = u64;
 type FileLoc = usize;
@@ -13,14 +20,15 @@ struct FSFile {
     start: FileLoc,
 }
 
-struct FS {
+#[derive(Debug, Clone)]
+pub struct FS {
     blocks: Vec<FSFile>,
     free_block_indexes: Vec<FileLoc>,
     free_blocks: BTreeMap<FileLoc, FileSize>,
 }
 
 impl FS {
-    fn new(input: String) -> Self {
+    fn new(input: &str) -> Self {
         let mut next_id = 0;
         let mut empty = false;
         let mut blocks = Vec::new();
@@ -74,56 +82,81 @@ impl Display for FS {
     }
 }
 
-pub fn part1(input: String) -> u64 {
-    let mut fs = FS::new(input);
-    let mut swaps = Vec::new();
-    fs.blocks
-        .iter()
-        .enumerate()
-        .filter_map(|(loc, f)| f.id.and(Some(loc)))
-        .rev()
-        .for_each(|file_loc| {
-            if let Some((free_idx, free_loc)) = fs
-                .free_block_indexes
-                .iter()
-                .enumerate()
-                .find_map(|(idx, &loc)| (loc < file_loc).then_some((idx, loc)))
-            {
-                fs.free_block_indexes.remove(free_idx);
-                swaps.push((file_loc, free_loc));
-            }
-        });
-    for (a, b) in swaps {
-        fs.blocks.swap(a, b);
+pub struct Day09;
+
+impl Solution for Day09 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 9;
+
+    type Config = ();
+    type Input = FS;
+
+    fn parse(input: &str, _: &()) -> Result<Self::Input, ParseError> {
+        if let Some(i) = input.find(|c: char| !c.is_ascii_digit()) {
+            let found = input[i..].chars().next().unwrap();
+            return Err(Parser::new(input).error(
+                &input[i..],
+                format!("expected a digit, found `{found}`"),
+            ));
+        }
+        Ok(FS::new(input))
     }
-    fs.checksum()
-}
 
-pub fn part2(input: String) -> u64 {
-    let mut fs = FS::new(input);
-    let mut swaps = Vec::new();
-    fs.blocks
-        .iter()
-        .unique()
-        .filter(|b| b.id.is_some())
-        .rev()
-        .for_each(|file| {
-            if let Some((&free_loc, _)) = fs
-                .free_blocks
-                .iter()
-                .find(|&(&free_loc, &size)| free_loc < file.start && size >= file.size)
-            {
-                (0..file.size)
-                    .for_each(|i| swaps.push((file.start + i as usize, free_loc + i as usize)));
-                let new_free_size = fs.free_blocks.remove(&free_loc).unwrap() - file.size;
-                if new_free_size > 0 {
-                    fs.free_blocks
-                        .insert(free_loc + file.size as usize, new_free_size);
+    fn part1(fs: &Self::Input) -> Answer {
+        let mut fs = fs.clone();
+        let mut swaps = Vec::new();
+        fs.blocks
+            .iter()
+            .enumerate()
+            .filter_map(|(loc, f)| f.id.and(Some(loc)))
+            .rev()
+            .for_each(|file_loc| {
+                if let Some((free_idx, free_loc)) = fs
+                    .free_block_indexes
+                    .iter()
+                    .enumerate()
+                    .find_map(|(idx, &loc)| (loc < file_loc).then_some((idx, loc)))
+                {
+                    fs.free_block_indexes.remove(free_idx);
+                    swaps.push((file_loc, free_loc));
                 }
-            }
-        });
-    for (a, b) in swaps {
-        fs.blocks.swap(a, b);
+            });
+        for (a, b) in swaps {
+            fs.blocks.swap(a, b);
+        }
+        Answer::from(fs.checksum())
+    }
+
+    fn part2(fs: &Self::Input) -> Answer {
+        let mut fs = fs.clone();
+        let mut swaps = Vec::new();
+        fs.blocks
+            .iter()
+            .unique()
+            .filter(|b| b.id.is_some())
+            .rev()
+            .for_each(|file| {
+                if let Some((&free_loc, _)) = fs
+                    .free_blocks
+                    .iter()
+                    .find(|&(&free_loc, &size)| free_loc < file.start && size >= file.size)
+                {
+                    (0..file.size)
+                        .for_each(|i| swaps.push((file.start + i as usize, free_loc + i as usize)));
+                    let new_free_size = fs.free_blocks.remove(&free_loc).unwrap() - file.size;
+                    if new_free_size > 0 {
+                        fs.free_blocks
+                            .insert(free_loc + file.size as usize, new_free_size);
+                    }
+                }
+            });
+        for (a, b) in swaps {
+            fs.blocks.swap(a, b);
+        }
+        Answer::from(fs.checksum())
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(1928).part2(2858)]
     }
-    fs.checksum()
 }
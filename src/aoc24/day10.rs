@@ -1,9 +1,21 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
 use grid::Grid;
 use itertools::Itertools;
 
-use crate::util::IsValidIndex;
+use crate::{
+    solution::{Example, Solution},
+    util::{Answer, Coordinate, ParseError, Parser},
+};
+
+const EXAMPLE_INPUT: &str = r"89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732";
 
 fn find_trails(
     grid: &Grid<u8>,
@@ -15,23 +27,11 @@ fn find_trails(
             current_positions
                 .iter()
                 .flat_map(|pos| {
-                    let last_pos = pos.iter().last().unwrap();
-                    [(0, -1), (0, 1), (1, 0), (-1, 0)]
-                        .into_iter()
-                        .filter_map(|d| {
-                            let new_pos = (last_pos.0 as isize + d.0, last_pos.1 as isize + d.1);
-                            (grid.is_valid_index(new_pos)
-                                && grid
-                                    .get(new_pos.0, new_pos.1)
-                                    .is_some_and(|&c| c == num_to_find))
-                            .then_some((new_pos.0 as usize, new_pos.1 as usize))
-                        })
-                        .map(|new_pos| {
-                            pos.iter()
-                                .copied()
-                                .chain([new_pos].into_iter())
-                                .collect_vec()
-                        })
+                    let last_pos = Coordinate::from(pos.iter().last().unwrap());
+                    last_pos
+                        .neighbors4()
+                        .filter(|n| grid.get(n.row(), n.col()) == Some(&num_to_find))
+                        .map(|new_pos| pos.iter().copied().chain([new_pos.into()]).collect_vec())
                 })
                 .collect_vec()
         },
@@ -42,45 +42,55 @@ fn find_trails(
         .collect()
 }
 
-pub fn part1(input: String) -> u64 {
-    let grid = Grid::from_vec(
-        input.lines().flat_map(|l| l.bytes()).collect_vec(),
-        input.lines().next().unwrap().len(),
-    );
-    let start_positions = grid
-        .indexed_iter()
-        .filter_map(|((x, y), &c)| (c == b'0').then_some((x, y)))
-        .collect_vec();
-    let trails = start_positions
-        .iter()
-        .flat_map(|&start_position| find_trails(&grid, start_position))
-        .unique()
-        .collect_vec();
-    let mut scores = HashMap::new();
-    trails.into_iter().for_each(|(start, _)| {
-        let score = scores.entry(start).or_default();
-        *score += 1;
-    });
-    scores.values().sum()
-}
+pub struct Day10;
 
-pub fn part2(input: String) -> u64 {
-    let grid = Grid::from_vec(
-        input.lines().flat_map(|l| l.bytes()).collect_vec(),
-        input.lines().next().unwrap().len(),
-    );
-    let start_positions = grid
-        .indexed_iter()
-        .filter_map(|((x, y), &c)| (c == b'0').then_some((x, y)))
-        .collect_vec();
-    let trails = start_positions
-        .iter()
-        .flat_map(|&start_position| find_trails(&grid, start_position))
-        .collect_vec();
-    let mut scores = HashMap::new();
-    trails.into_iter().for_each(|(start, _)| {
-        let score = scores.entry(start).or_default();
-        *score += 1;
-    });
-    scores.values().sum()
+impl Solution for Day10 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 10;
+
+    type Config = ();
+    type Input = Grid<u8>;
+
+    fn parse(input: &str, _: &()) -> Result<Self::Input, ParseError> {
+        Parser::new(input).grid(input)
+    }
+
+    fn part1(grid: &Self::Input) -> Answer {
+        let start_positions = grid
+            .indexed_iter()
+            .filter_map(|((x, y), &c)| (c == b'0').then_some((x, y)))
+            .collect_vec();
+        let trails = start_positions
+            .iter()
+            .flat_map(|&start_position| find_trails(grid, start_position))
+            .unique()
+            .collect_vec();
+        let mut scores = HashMap::new();
+        trails.into_iter().for_each(|(start, _)| {
+            let score = scores.entry(start).or_default();
+            *score += 1;
+        });
+        Answer::from(scores.values().sum::<u64>())
+    }
+
+    fn part2(grid: &Self::Input) -> Answer {
+        let start_positions = grid
+            .indexed_iter()
+            .filter_map(|((x, y), &c)| (c == b'0').then_some((x, y)))
+            .collect_vec();
+        let trails = start_positions
+            .iter()
+            .flat_map(|&start_position| find_trails(grid, start_position))
+            .collect_vec();
+        let mut scores = HashMap::new();
+        trails.into_iter().for_each(|(start, _)| {
+            let score = scores.entry(start).or_default();
+            *score += 1;
+        });
+        Answer::from(scores.values().sum::<u64>())
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(36).part2(81)]
+    }
 }
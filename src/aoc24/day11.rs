@@ -1,6 +1,12 @@
 use cached::proc_macro::cached;
-use itertools::Itertools;
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+use crate::{
+    solution::{Example, Solution},
+    util::{Answer, ParseError, Parser},
+};
+
+const EXAMPLE_INPUT: &str = r"125 17";
 
 #[cached]
 fn update_stone(stone: u64, count: u8) -> u64 {
@@ -8,36 +14,50 @@ fn update_stone(stone: u64, count: u8) -> u64 {
         return 1;
     }
     if stone == 0 {
-        return update_stone(1, count - 1);
+        update_stone(1, count - 1)
     } else {
         let digits = stone.ilog10() + 1;
-        if digits % 2 == 0 {
-            return update_stone(stone / 10u64.pow(digits / 2), count - 1)
-                + update_stone(stone % 10u64.pow(digits / 2), count - 1);
+        if digits.is_multiple_of(2) {
+            update_stone(stone / 10u64.pow(digits / 2), count - 1)
+                + update_stone(stone % 10u64.pow(digits / 2), count - 1)
         } else {
-            return update_stone(stone * 2024, count - 1);
+            update_stone(stone * 2024, count - 1)
         }
     }
 }
 
-pub fn part1(input: String) -> u64 {
-    let stones: Vec<u64> = input
-        .split_ascii_whitespace()
-        .map(|s| s.parse().unwrap())
-        .collect_vec();
-    return stones
-        .into_par_iter()
-        .map(|stone| update_stone(stone, 25))
-        .sum();
-}
+pub struct Day11;
+
+impl Solution for Day11 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 11;
+
+    type Config = ();
+    type Input = Vec<u64>;
+
+    fn parse(input: &str, _: &()) -> Result<Self::Input, ParseError> {
+        Parser::new(input).words(input)
+    }
+
+    fn part1(stones: &Self::Input) -> Answer {
+        Answer::from(
+            stones
+                .par_iter()
+                .map(|&stone| update_stone(stone, 25))
+                .sum::<u64>(),
+        )
+    }
 
-pub fn part2(input: String) -> u64 {
-    let stones: Vec<u64> = input
-        .split_ascii_whitespace()
-        .map(|s| s.parse().unwrap())
-        .collect_vec();
-    return stones
-        .into_par_iter()
-        .map(|stone| update_stone(stone, 75))
-        .sum();
+    fn part2(stones: &Self::Input) -> Answer {
+        Answer::from(
+            stones
+                .par_iter()
+                .map(|&stone| update_stone(stone, 75))
+                .sum::<u64>(),
+        )
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(55312)]
+    }
 }
@@ -3,7 +3,21 @@ use rustc_hash::FxHashSet as HashSet;
 use grid::Grid;
 use itertools::Itertools;
 
-use crate::util::{adj_squares, adj_squares8, diff};
+use crate::{
+    solution::{Example, Solution},
+    util::{adj_squares, adj_squares8, diff, Answer, ParseError, Parser},
+};
+
+const EXAMPLE_INPUT: &str = r"RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE";
 
 fn find_regions(grid: &Grid<u8>, include_sides: bool) -> Vec<(usize, usize, usize)> {
     let mut regions = Vec::new();
@@ -20,7 +34,7 @@ fn find_regions(grid: &Grid<u8>, include_sides: bool) -> Vec<(usize, usize, usiz
             let mut sides = 0;
             let mut squares = HashSet::default();
             squares.insert((x, y));
-            while adj.len() > 0 {
+            while !adj.is_empty() {
                 adj = adj
                     .into_iter()
                     .flat_map(|pos| {
@@ -76,19 +90,30 @@ fn find_regions(grid: &Grid<u8>, include_sides: bool) -> Vec<(usize, usize, usiz
     regions
 }
 
-pub fn part1(input: String) -> u64 {
-    let grid = Grid::from_vec(
-        input.lines().flat_map(|l| l.bytes()).collect_vec(),
-        input.lines().next().unwrap().len(),
-    );
-    let regions = find_regions(&grid, false);
-    regions.into_iter().map(|(p, a, _)| p * a).sum::<usize>() as u64
-}
-pub fn part2(input: String) -> u64 {
-    let grid = Grid::from_vec(
-        input.lines().flat_map(|l| l.bytes()).collect_vec(),
-        input.lines().next().unwrap().len(),
-    );
-    let regions = find_regions(&grid, true);
-    regions.into_iter().map(|(_, a, s)| s * a).sum::<usize>() as u64
+pub struct Day12;
+
+impl Solution for Day12 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 12;
+
+    type Config = ();
+    type Input = Grid<u8>;
+
+    fn parse(input: &str, _: &()) -> Result<Self::Input, ParseError> {
+        Parser::new(input).grid(input)
+    }
+
+    fn part1(grid: &Self::Input) -> Answer {
+        let regions = find_regions(grid, false);
+        Answer::from(regions.into_iter().map(|(p, a, _)| p * a).sum::<usize>() as u64)
+    }
+
+    fn part2(grid: &Self::Input) -> Answer {
+        let regions = find_regions(grid, true);
+        Answer::from(regions.into_iter().map(|(_, a, s)| s * a).sum::<usize>() as u64)
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(1930).part2(1206)]
+    }
 }
@@ -1,67 +1,88 @@
-use itertools::Itertools;
+use crate::{
+    math::Matrix,
+    solution::{Example, Solution},
+    util::{Answer, ParseError, Parser},
+};
 
-use crate::math::Matrix;
+const EXAMPLE_INPUT: &str = r"Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
 
-#[derive(Debug)]
-struct Machine {
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279";
+
+#[derive(Debug, Clone, Copy)]
+pub struct Machine {
     a: (usize, usize),
     b: (usize, usize),
     goal: (usize, usize),
 }
 
-fn parse(input: String) -> Machine {
+fn parse_machine<'a>(p: &Parser<'a>, input: &'a str) -> Result<Machine, ParseError> {
     let mut lines = input.lines();
-    let a = lines.next().unwrap()[10..]
-        .split(", ")
-        .map(|s| s[2..].parse().unwrap())
-        .collect_tuple()
-        .unwrap();
-    let b = lines.next().unwrap()[10..]
-        .split(", ")
-        .map(|s| s[2..].parse().unwrap())
-        .collect_tuple()
-        .unwrap();
-    let goal = lines.next().unwrap()[7..]
-        .split(", ")
-        .map(|s| s[2..].parse().unwrap())
-        .collect_tuple()
-        .unwrap();
-    Machine { a, b, goal }
+    let mut coords = |prefix: &str, sign: &str| -> Result<(usize, usize), ParseError> {
+        let line = p.next(&mut lines, input, &format!("`{prefix}`"))?;
+        let (x, y) = p.split_once(p.strip_prefix(line, prefix)?, ", ")?;
+        Ok((
+            p.num(p.strip_prefix(x, &format!("X{sign}"))?)?,
+            p.num(p.strip_prefix(y, &format!("Y{sign}"))?)?,
+        ))
+    };
+    Ok(Machine {
+        a: coords("Button A: ", "+")?,
+        b: coords("Button B: ", "+")?,
+        goal: coords("Prize: ", "=")?,
+    })
 }
 
-pub fn part1(input: String) -> u64 {
-    let machines = input
-        .split("\n\n")
-        .map(str::to_string)
-        .map(parse)
-        .collect_vec();
-    machines
-        .into_iter()
-        .map(|machine| {
-            let m = Matrix::new(machine.a.0, machine.b.0, machine.a.1, machine.b.1);
-            let solution = m.solve(machine.goal.0, machine.goal.1);
-            solution.map(|(a, b)| a * 3 + b).unwrap_or_default()
-        })
-        .sum::<usize>() as u64
+fn cost(machine: &Machine) -> usize {
+    let m = Matrix::new(machine.a.0, machine.b.0, machine.a.1, machine.b.1);
+    let solution = m.solve(machine.goal.0, machine.goal.1);
+    solution.map(|(a, b)| a * 3 + b).unwrap_or_default()
 }
 
-pub fn part2(input: String) -> u64 {
-    let machines = input
-        .split("\n\n")
-        .map(str::to_string)
-        .map(|s| {
-            let mut m = parse(s);
-            m.goal.0 += 10000000000000;
-            m.goal.1 += 10000000000000;
-            m
-        })
-        .collect_vec();
-    machines
-        .into_iter()
-        .map(|machine| {
-            let m = Matrix::new(machine.a.0, machine.b.0, machine.a.1, machine.b.1);
-            let solution = m.solve(machine.goal.0, machine.goal.1);
-            solution.map(|(a, b)| a * 3 + b).unwrap_or_default()
-        })
-        .sum::<usize>() as u64
+pub struct Day13;
+
+impl Solution for Day13 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 13;
+
+    type Config = ();
+    type Input = Vec<Machine>;
+
+    fn parse(input: &str, _: &()) -> Result<Self::Input, ParseError> {
+        let p = Parser::new(input);
+        input.split("\n\n").map(|m| parse_machine(&p, m)).collect()
+    }
+
+    fn part1(machines: &Self::Input) -> Answer {
+        Answer::from(machines.iter().map(cost).sum::<usize>() as u64)
+    }
+
+    fn part2(machines: &Self::Input) -> Answer {
+        Answer::from(
+            machines
+                .iter()
+                .map(|&machine| {
+                    let mut m = machine;
+                    m.goal.0 += 10000000000000;
+                    m.goal.1 += 10000000000000;
+                    cost(&m)
+                })
+                .sum::<usize>() as u64,
+        )
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(480)]
+    }
 }
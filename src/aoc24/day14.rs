@@ -1,48 +1,84 @@
 use grid::Grid;
 use itertools::Itertools;
 
-use crate::util::adj_squares;
+use crate::{
+    solution::{Example, Solution},
+    util::{Answer, Coordinate, ParseError, Parser, Point},
+};
 
-const WIDTH: i16 = 101;
-const HEIGHT: i16 = 103;
+const EXAMPLE_INPUT: &str = r"p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3";
 
 #[derive(Debug, Clone, Copy)]
-struct Robot {
-    x: i16,
-    y: i16,
-    vx: i16,
-    vy: i16,
+pub struct Config {
+    pub width: i16,
+    pub height: i16,
+}
+
+impl Config {
+    fn size(&self) -> Point<i16> {
+        Point::new(self.width, self.height)
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            width: 101,
+            height: 103,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Robot {
+    pos: Point<i16>,
+    vel: Point<i16>,
 }
 
 impl Robot {
-    pub fn parse(line: &str) -> Self {
-        let (x, y, vx, vy) = line
-            .split_ascii_whitespace()
-            .flat_map(|s| s[2..].split(",").map(|x| x.parse::<i16>().unwrap()))
-            .collect_tuple()
-            .unwrap();
-        Self { x, y, vx, vy }
+    pub fn parse<'a>(p: &Parser<'a>, line: &'a str) -> Result<Self, ParseError> {
+        let (pos, vel) = p.split_once(line, " ")?;
+        Ok(Self {
+            pos: p.pair(p.strip_prefix(pos, "p=")?, ",")?.into(),
+            vel: p.pair(p.strip_prefix(vel, "v=")?, ",")?.into(),
+        })
+    }
+
+    pub fn step(self, config: &Config) -> Self {
+        let pos = (self.pos + self.vel).rem_euclid(config.size());
+        Self { pos, ..self }
     }
 
-    pub fn step(self) -> Self {
-        let x = (self.x + self.vx + WIDTH) % WIDTH;
-        let y = (self.y + self.vy + HEIGHT) % HEIGHT;
-        Self { x, y, ..self }
+    /// The robot's cell on a grid with a row per `y` and a column per `x`; robots always stay
+    /// on the grid.
+    fn cell(&self) -> Coordinate {
+        let pos: Point<usize> = self.pos.try_cast().expect("robots stay on the grid");
+        Coordinate::from_row_col(pos.y, pos.x)
     }
 
-    pub fn step_mut(&mut self) {
-        self.x = (self.x + self.vx + WIDTH) % WIDTH;
-        self.y = (self.y + self.vy + HEIGHT) % HEIGHT;
+    pub fn step_mut(&mut self, config: &Config) {
+        self.pos = (self.pos + self.vel).rem_euclid(config.size());
     }
 }
 
-fn count_quads(robots: &Vec<Robot>) -> [u64; 4] {
-    let mid_x = WIDTH / 2;
-    let mid_y = HEIGHT / 2;
+fn count_quads(robots: &[Robot], config: &Config) -> [u64; 4] {
+    let mid_x = config.width / 2;
+    let mid_y = config.height / 2;
     let mut counts = [0; 4];
 
     for r in robots {
-        match ((r.x - mid_x).signum(), (r.y - mid_y).signum()) {
+        match ((r.pos.x - mid_x).signum(), (r.pos.y - mid_y).signum()) {
             (1, 1) => counts[0] += 1,
             (1, -1) => counts[1] += 1,
             (-1, -1) => counts[2] += 1,
@@ -54,33 +90,64 @@ fn count_quads(robots: &Vec<Robot>) -> [u64; 4] {
     counts
 }
 
-pub fn part1(input: String) -> u64 {
-    let mut robots = input.lines().map(Robot::parse).collect_vec();
-    for _ in 0..100 {
-        robots = robots.into_iter().map(Robot::step).collect_vec();
+pub struct Day14;
+
+impl Solution for Day14 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 14;
+
+    type Config = Config;
+    type Input = (Vec<Robot>, Config);
+
+    fn parse(input: &str, config: &Config) -> Result<Self::Input, ParseError> {
+        let p = Parser::new(input);
+        let robots = input
+            .lines()
+            .map(|l| Robot::parse(&p, l))
+            .collect::<Result<_, _>>()?;
+        Ok((robots, *config))
     }
 
-    count_quads(&robots).into_iter().product::<u64>()
-}
+    fn part1((robots, config): &Self::Input) -> Answer {
+        let mut robots = robots.clone();
+        for _ in 0..100 {
+            robots = robots.into_iter().map(|r| r.step(config)).collect_vec();
+        }
 
-pub fn part2(input: String) -> u64 {
-    let mut robots = input.lines().map(Robot::parse).collect_vec();
-    let mut grid = Grid::<u16>::new(WIDTH as usize, HEIGHT as usize);
-    for i in 1..(WIDTH * HEIGHT) as u16 {
-        for r in robots.iter_mut() {
-            r.step_mut();
-            grid[(r.x as usize, r.y as usize)] = i;
+        Answer::from(count_quads(&robots, config).into_iter().product::<u64>())
+    }
+
+    fn part2((robots, config): &Self::Input) -> Answer {
+        let mut robots = robots.clone();
+        let mut grid = Grid::<u32>::new(config.height as usize, config.width as usize);
+        // The robots are all back where they started after `width * height` seconds.
+        for i in 1..config.width as u32 * config.height as u32 {
+            for r in robots.iter_mut() {
+                r.step_mut(config);
+                grid[r.cell()] = i;
+            }
+            let num_robots_with_adj: usize = robots
+                .iter()
+                .map(|r| {
+                    r.cell()
+                        .neighbors4()
+                        .filter(|n| grid.get(n.row(), n.col()) == Some(&i))
+                        .count()
+                })
+                .sum();
+            if num_robots_with_adj >= robots.len() / 10 * 9 {
+                return Answer::from(i as u64);
+            }
         }
-        let num_robots_with_adj: usize = robots
-            .iter()
-            .map(|r| {
-                let adj = adj_squares(&grid, (r.x as usize, r.y as usize));
-                adj.into_iter().filter(|&pos| grid[pos] == i).count()
+        Answer::None
+    }
+
+    fn examples() -> Vec<Example<Config>> {
+        vec![Example::new(EXAMPLE_INPUT)
+            .config(Config {
+                width: 11,
+                height: 7,
             })
-            .sum();
-        if num_robots_with_adj >= robots.len() / 10 * 9 {
-            return i as u64;
-        }
+            .part1(12)]
     }
-    unreachable!();
 }
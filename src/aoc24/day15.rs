@@ -1,9 +1,34 @@
-use std::{cell::RefCell, collections::HashMap, fmt::Display};
+use std::{collections::HashMap, fmt::Display};
 
 use grid::Grid;
 use itertools::Itertools;
 
-use crate::util::{Coordinate, Direction, GridRef, IsValidIndex};
+use crate::{
+    solution::{Example, Solution},
+    util::{Answer, Coordinate, Direction, Gridtools, ParseError, Parser},
+};
+
+const EXAMPLE_INPUT: &str = r"##########
+#..O..O.O#
+#......O.#
+#.OO..O.O#
+#..O@..O.#
+#O#..O...#
+#O..O..O.#
+#.OO.O.OO#
+#....O...#
+##########
+
+<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
+vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
+><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
+<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
+^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
+^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
+>^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
+<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
+^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
+v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Box {
@@ -28,9 +53,7 @@ impl GridSpace {
         pos: Coordinate,
         check_box_pair: bool,
     ) -> Option<Vec<Coordinate>> {
-        let next_pos_offset = dir.move_dir(pos);
-        if grid.is_valid_index(next_pos_offset) {
-            let next_pos: Coordinate = next_pos_offset.into();
+        if let Some(next_pos) = grid.try_move_dir(pos, dir) {
             return match (self, dir) {
                 (Self::EMPTY, _) => Some(vec![]),
                 (Self::WALL, _) => None,
@@ -80,35 +103,16 @@ impl Display for GridSpace {
     }
 }
 
-fn parse(input: String) -> (Grid<u8>, Vec<Direction>, (usize, usize)) {
-    let (map, moves) = input.split("\n\n").collect_tuple().unwrap();
-    let grid = Grid::from_vec(
-        map.lines().flat_map(|l| l.bytes()).collect_vec(),
-        map.lines().next().unwrap().len(),
-    );
-    let robot_pos = grid
-        .indexed_iter()
-        .find_map(|(pos, &c)| (c == b'@').then_some(pos))
-        .unwrap();
-    (
-        grid,
-        moves
-            .lines()
-            .flat_map(|l| l.bytes())
-            .map(Direction::from_char)
-            .collect_vec(),
-        robot_pos,
-    )
-}
-
 fn step(grid: &mut Grid<u8>, robot_pos: (usize, usize), dir: Direction) -> (usize, usize) {
     let mut prev_pos = robot_pos;
-    let mut next_pos = dir.move_dir(robot_pos.into());
-    let robot_target_pos = (next_pos.x as usize, next_pos.y as usize);
+    let Some(robot_target_pos) = grid.try_move_dir(robot_pos.into(), dir) else {
+        return robot_pos;
+    };
+    let mut next_pos = Some(robot_target_pos);
     let mut found_space = false;
     let mut updates = HashMap::new();
-    while grid.is_valid_index(next_pos) {
-        let pos = (next_pos.x as usize, next_pos.y as usize);
+    while let Some(next) = next_pos {
+        let pos = next.into();
         if grid[pos] == b'#' {
             break;
         }
@@ -119,95 +123,63 @@ fn step(grid: &mut Grid<u8>, robot_pos: (usize, usize), dir: Direction) -> (usiz
         }
 
         prev_pos = pos;
-        next_pos = dir.move_dir(pos.into());
+        next_pos = grid.try_move_dir(next, dir);
     }
     if found_space {
         for (pos, c) in updates {
             grid[pos] = c;
         }
         grid[robot_pos] = b'.';
-        return robot_target_pos;
+        return robot_target_pos.into();
     }
     robot_pos
 }
 
-pub fn part1(input: String) -> u64 {
-    let (mut grid, moves, mut robot_pos) = parse(input);
-    // print_grid(&grid);
-    // println!();
-    for dir in moves {
-        robot_pos = step(&mut grid, robot_pos, dir);
-        // print_grid(&grid);
-        // println!();
-    }
-    grid.indexed_iter()
-        .filter_map(|(pos, &c)| (c == b'O').then_some(pos))
-        .fold(0, |acc, pos| acc + (100 * pos.0 + pos.1) as u64)
-}
-
-fn widen(input: String) -> String {
-    let (map, moves) = input.split("\n\n").collect_tuple().unwrap();
-    format!(
-        "{}\n\n{}",
-        map.bytes()
-            .map(|b| match b {
-                b'#' => "##",
-                b'O' => "[]",
-                b'.' => "..",
-                b'@' => "@.",
-                b'\n' => "\n",
+fn widen(grid: &Grid<u8>) -> Grid<u8> {
+    Grid::from_vec(
+        grid.iter()
+            .flat_map(|&b| match b {
+                b'#' => *b"##",
+                b'O' => *b"[]",
+                b'.' => *b"..",
+                b'@' => *b"@.",
                 _ => unreachable!("{}", String::from_utf8_lossy(&[b])),
             })
-            .join(""),
-        moves
+            .collect_vec(),
+        grid.cols() * 2,
     )
 }
 
-fn parse2(input: String) -> (Grid<GridSpace>, Vec<Direction>, Coordinate) {
-    let (map, moves) = input.split("\n\n").collect_tuple().unwrap();
-    let robot_pos = RefCell::new((0, 0));
-    let grid = Grid::from_vec(
-        map.lines()
-            .enumerate()
-            .flat_map(|(x, l)| {
-                let robot_pos = &robot_pos;
-                l.bytes().enumerate().filter_map(move |(y, b)| match b {
-                    b'#' => Some(GridSpace::WALL),
-                    b'@' => {
-                        robot_pos.replace((x, y));
-                        Some(GridSpace::ROBOT)
-                    }
-                    b'.' => Some(GridSpace::EMPTY),
-                    b'[' => Some(GridSpace::BoxLeft(Box {
-                        left: (x, y).into(),
-                        right: (x, y + 1).into(),
-                    })),
-                    b']' => Some(GridSpace::BoxRight(Box {
-                        left: (x, y - 1).into(),
-                        right: (x, y).into(),
-                    })),
-                    _ => unreachable!(),
-                })
-            })
-            .collect_vec(),
-        map.lines().next().unwrap().trim().len(),
-    );
-    (
-        grid,
-        moves
-            .lines()
-            .flat_map(|l| l.bytes())
-            .map(Direction::from_char)
-            .collect_vec(),
-        robot_pos.take().into(),
-    )
+fn to_spaces(grid: &Grid<u8>) -> (Grid<GridSpace>, Coordinate) {
+    let mut robot_pos = (0, 0);
+    let spaces = grid
+        .indexed_iter()
+        .map(|((x, y), &b)| match b {
+            b'#' => GridSpace::WALL,
+            b'@' => {
+                robot_pos = (x, y);
+                GridSpace::ROBOT
+            }
+            b'.' => GridSpace::EMPTY,
+            b'[' => GridSpace::BoxLeft(Box {
+                left: (x, y).into(),
+                right: (x, y + 1).into(),
+            }),
+            b']' => GridSpace::BoxRight(Box {
+                left: (x, y - 1).into(),
+                right: (x, y).into(),
+            }),
+            _ => unreachable!(),
+        })
+        .collect_vec();
+    (Grid::from_vec(spaces, grid.cols()), robot_pos.into())
 }
 
 fn step2(grid: &mut Grid<GridSpace>, robot_pos: Coordinate, dir: Direction) -> Coordinate {
     if let Some(v) = grid[robot_pos].can_move(grid, dir, robot_pos, true) {
         let mut updates = HashMap::new();
         for &pos in v.iter() {
-            let next_pos: Coordinate = dir.move_dir(pos).into();
+            let next_pos = pos + dir.to_delta();
             updates.insert(next_pos, grid[pos]);
         }
         for pos in v {
@@ -217,12 +189,12 @@ fn step2(grid: &mut Grid<GridSpace>, robot_pos: Coordinate, dir: Direction) -> C
             if let GridSpace::BoxLeft(_) = g {
                 let b = Box {
                     left: c,
-                    right: (c.x, c.y + 1).into(),
+                    right: c + Direction::RIGHT.to_delta(),
                 };
                 grid[c] = GridSpace::BoxLeft(b);
             } else if let GridSpace::BoxRight(_) = g {
                 let b = Box {
-                    left: (c.x, c.y - 1).into(),
+                    left: c + Direction::LEFT.to_delta(),
                     right: c,
                 };
                 grid[c] = GridSpace::BoxRight(b);
@@ -230,35 +202,58 @@ fn step2(grid: &mut Grid<GridSpace>, robot_pos: Coordinate, dir: Direction) -> C
                 grid[c] = g;
             }
         }
-        return dir.move_dir(robot_pos).into();
+        return robot_pos + dir.to_delta();
     }
     robot_pos
 }
 
-pub fn part2(input: String) -> u64 {
-    let (mut grid, moves, mut robot_pos) = parse2(widen(input));
-    // print_grid(&grid);
-    // println!();
-    // println!("Initial state:");
-    // print_grid2(&grid);
-    // println!();
-    for dir in moves {
-        // println!("Move {dir}:");
-        robot_pos = step2(&mut grid, robot_pos, dir);
-        // print_grid2(&grid);
-        // println!();
+pub struct Day15;
+
+impl Solution for Day15 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 15;
+
+    type Config = ();
+    type Input = (Grid<u8>, Vec<Direction>, (usize, usize));
+
+    fn parse(input: &str, _: &()) -> Result<Self::Input, ParseError> {
+        let p = Parser::new(input);
+        let (map, moves) = p.split_once(input, "\n\n")?;
+        p.only(map, b"#.O@", "a wall, box, robot or floor")?;
+        let grid = p.grid(map)?;
+        p.walled(map, &grid, b'#')?;
+        let robot_pos = p.find(map, &grid, b'@')?;
+        p.only(moves, b"<>^v", "a move")?;
+        let moves = moves.bytes().filter_map(Direction::from_char).collect();
+        Ok((grid, moves, robot_pos))
     }
-    // print_grid2(&grid);
-    grid.indexed_iter()
-        .filter_map(|(pos, &ref c)| {
-            if let GridSpace::BoxLeft(_) = c {
-                return Some(pos);
-            }
-            None
-        })
-        .fold(0, |acc, pos| acc + (100 * pos.0 + pos.1) as u64)
-}
 
-fn print_grid2(grid: &Grid<GridSpace>) {
-    println!("{}", GridRef::from(grid));
+    fn part1((grid, moves, robot_pos): &Self::Input) -> Answer {
+        let mut grid = grid.clone();
+        let mut robot_pos = *robot_pos;
+        for &dir in moves {
+            robot_pos = step(&mut grid, robot_pos, dir);
+        }
+        Answer::from(
+            grid.indexed_iter()
+                .filter_map(|(pos, &c)| (c == b'O').then_some(pos))
+                .fold(0u64, |acc, pos| acc + (100 * pos.0 + pos.1) as u64),
+        )
+    }
+
+    fn part2((grid, moves, _): &Self::Input) -> Answer {
+        let (mut grid, mut robot_pos) = to_spaces(&widen(grid));
+        for &dir in moves {
+            robot_pos = step2(&mut grid, robot_pos, dir);
+        }
+        Answer::from(
+            grid.indexed_iter()
+                .filter_map(|(pos, c)| matches!(c, GridSpace::BoxLeft(_)).then_some(pos))
+                .fold(0u64, |acc, pos| acc + (100 * pos.0 + pos.1) as u64),
+        )
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(10092).part2(9021)]
+    }
 }
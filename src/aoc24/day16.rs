@@ -1,63 +1,44 @@
-use std::{
-    cell::RefCell,
-    collections::{HashMap, HashSet},
-    fmt::Display,
-};
+use std::collections::{HashMap, HashSet};
 
 use exhaust::Exhaust;
 use itertools::Itertools;
-use pathfinding::{
-    grid::Grid,
-    matrix::Matrix,
-    prelude::{astar_bag, dijkstra_partial},
-};
-use petgraph::{
-    algo::{all_simple_paths, dijkstra},
-    data::DataMap,
-    dot::Dot,
-    prelude::*,
-};
+use pathfinding::prelude::astar_bag;
+use petgraph::{algo::dijkstra, prelude::*};
 
-use crate::util::{adj_squares, Coordinate, Direction};
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Node(Coordinate, Direction);
+use crate::{
+    solution::{Example, Solution},
+    util::{Answer, Coordinate, Direction, Heading, ParseError, Parser, Turn},
+};
 
-impl<T> From<(T, Direction)> for Node
-where
-    T: Into<Coordinate>,
-{
-    fn from(value: (T, Direction)) -> Self {
-        Self(value.0.into(), value.1)
-    }
-}
+const EXAMPLE_INPUT: &str = r"###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############";
 
-impl Display for Node {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} {}", self.0, self.1)
-    }
-}
+/// The maze as a graph of positions and facings, with the start node and every end node.
+type Maze = (Graph<Heading, usize>, NodeIndex, Vec<NodeIndex>);
 
-fn parse(input: String) -> (Graph<Node, usize>, NodeIndex, Vec<NodeIndex>) {
-    let grid = grid::Grid::from_vec(
-        input.lines().flat_map(|l| l.trim().bytes()).collect_vec(),
-        input.lines().next().unwrap().len(),
-    );
+fn build_graph(input: &str) -> Result<Maze, ParseError> {
+    let p = Parser::new(input);
+    p.only(input, b"#.SE", "a wall, floor, start or end")?;
+    let grid = p.grid(input)?;
+    p.walled(input, &grid, b'#')?;
     let mut g = Graph::new();
-    let start: Coordinate = grid
-        .indexed_iter()
-        .find(|&(_, &c)| c == b'S')
-        .unwrap()
-        .0
-        .into();
-    let end: Coordinate = grid
-        .indexed_iter()
-        .find(|&(_, &c)| c == b'E')
-        .unwrap()
-        .0
-        .into();
+    let start: Coordinate = p.find(input, &grid, b'S')?.into();
+    let end: Coordinate = p.find(input, &grid, b'E')?.into();
 
-    let mut node_indexes: HashMap<Node, NodeIndex> = HashMap::new();
+    let mut node_indexes: HashMap<Heading, NodeIndex> = HashMap::new();
     Direction::exhaust().for_each(|dir| {
         let idx1 = g.add_node((start, dir).into());
         let idx2 = g.add_node((end, dir).into());
@@ -67,72 +48,84 @@ fn parse(input: String) -> (Graph<Node, usize>, NodeIndex, Vec<NodeIndex>) {
     grid.indexed_iter().for_each(|(pos, &c)| {
         if c != b'#' {
             for dir in Direction::exhaust() {
-                let coord = (pos, dir).into();
+                let node = Heading::from((pos, dir));
                 let idx = *node_indexes
-                    .entry(coord)
-                    .or_insert_with(|| g.add_node(coord));
-                let next_pos: Coordinate = dir.move_dir(pos.into()).into();
-                if grid[next_pos] != b'#' {
-                    let coord2 = (next_pos, dir).into();
+                    .entry(node)
+                    .or_insert_with(|| g.add_node(node));
+                let next = node.advance().filter(|next| {
+                    grid.get(next.pos.row(), next.pos.col())
+                        .is_some_and(|&c| c != b'#')
+                });
+                if let Some(next) = next {
                     let idx2 = *node_indexes
-                        .entry(coord2)
-                        .or_insert_with(|| g.add_node(coord2));
+                        .entry(next)
+                        .or_insert_with(|| g.add_node(next));
                     g.add_edge(idx, idx2, 1);
                 }
             }
         }
     });
     node_indexes.iter().for_each(|(&node, &idx)| {
-        let idx2 = node_indexes[&(node.0, node.1.cw()).into()];
+        let idx2 = node_indexes[&node.turn(Turn::RIGHT)];
         g.add_edge(idx, idx2, 1000);
-        let idx3 = node_indexes[&(node.0, node.1.cw().opposite()).into()];
+        let idx3 = node_indexes[&node.turn(Turn::LEFT)];
         g.add_edge(idx, idx3, 1000);
     });
 
     let start_node = node_indexes[&(start, Direction::RIGHT).into()];
     let end_nodes = node_indexes
         .iter()
-        .filter_map(|(&k, &v)| (k.0 == end).then_some(v))
+        .filter_map(|(&k, &v)| (k.pos == end).then_some(v))
         .collect_vec();
-    (g, start_node, end_nodes)
+    Ok((g, start_node, end_nodes))
 }
 
-pub fn part1(input: String) -> u64 {
-    let (g, start, ends) = parse(input);
-    // println!("{} {}", g.node_count(), g.edge_count());
-    let sol = dijkstra(&g, start, None, |edge| *edge.weight());
-    // dbg!(start, &ends, &sol);
-    // println!("{}", Dot::with_config(&g, &[]));
-    ends.into_iter()
-        .filter_map(|end| sol.get(&end).copied())
-        .min()
-        .unwrap() as u64
-    // 0
-}
+pub struct Day16;
 
-pub fn part2(input: String) -> u64 {
-    let (g, start, ends) = parse(input);
-    let res: HashSet<_> = astar_bag(
-        &start,
-        |&idx| {
-            let edges = g.edges(idx);
-            edges.map(|edge| (edge.target(), *edge.weight()))
-        },
-        |&idx| {
-            let node = g[idx];
-            ends.iter()
-                .map(|&end_idx| {
-                    let end = g[end_idx];
-                    node.0.x.abs_diff(end.0.x) + node.0.y.abs_diff(end.0.y)
-                })
-                .min()
-                .unwrap()
-        },
-        |idx| ends.contains(idx),
-    )
-    .unwrap()
-    .0
-    .flat_map(|p| p.into_iter().map(|idx| g[idx].0))
-    .collect();
-    res.len() as u64
+impl Solution for Day16 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 16;
+
+    type Config = ();
+    type Input = Maze;
+
+    fn parse(input: &str, _: &()) -> Result<Self::Input, ParseError> {
+        build_graph(input)
+    }
+
+    fn part1((g, start, ends): &Self::Input) -> Answer {
+        let sol = dijkstra(g, *start, None, |edge| *edge.weight());
+        Answer::from(ends.iter().filter_map(|end| sol.get(end).copied()).min())
+    }
+
+    fn part2((g, start, ends): &Self::Input) -> Answer {
+        let Some((paths, _)) = astar_bag(
+            start,
+            |&idx| {
+                let edges = g.edges(idx);
+                edges.map(|edge| (edge.target(), *edge.weight()))
+            },
+            |&idx| {
+                let node = g[idx];
+                ends.iter()
+                    .map(|&end_idx| {
+                        let end = g[end_idx];
+                        node.pos.manhattan(end.pos)
+                    })
+                    .min()
+                    .unwrap()
+            },
+            |idx| ends.contains(idx),
+        ) else {
+            return Answer::None;
+        };
+        let res: HashSet<_> = paths
+            .flat_map(|p| p.into_iter().map(|idx| g[idx].pos))
+            .collect();
+        Answer::from(res.len() as u64)
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(7036).part2(45)]
+    }
 }
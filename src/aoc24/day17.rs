@@ -1,13 +1,26 @@
-use std::{
-    collections::{BTreeMap, HashMap, HashSet},
-    fmt::Display,
-};
+use std::{collections::HashSet, fmt::Display};
 
 use itertools::Itertools;
-use rayon::prelude::*;
+
+use crate::{
+    solution::{Example, Solution},
+    util::{Answer, ParseError, Parser},
+};
+
+const EXAMPLE_INPUT: &str = r"Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0";
+
+const EXAMPLE_INPUT_2: &str = r"Register A: 2024
+Register B: 0
+Register C: 0
+
+Program: 0,3,5,4,3,0";
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
-struct Computer {
+pub struct Computer {
     pub a: usize,
     pub b: usize,
     pub c: usize,
@@ -28,32 +41,42 @@ impl Computer {
         }
     }
 
-    fn combo(&self, operand: u8) -> usize {
+    fn combo(&self, operand: u8) -> Result<usize, Fault> {
         match operand {
-            0..=3 => operand as usize,
-            4 => self.a,
-            5 => self.b,
-            6 => self.c,
-            _ => unimplemented!(),
+            0..=3 => Ok(operand as usize),
+            4 => Ok(self.a),
+            5 => Ok(self.b),
+            6 => Ok(self.c),
+            _ => Err(Fault::ReservedOperand { ip: self.ip }),
         }
     }
 
-    pub fn step(&mut self) {
-        if self.ip >= self.program.len() - 1 {
-            return;
+    /// `self.a` shifted right by a combo operand, which can be wider than `usize`.
+    fn shifted_a(&self, operand: u8) -> Result<usize, Fault> {
+        let shift = self.combo(operand)?;
+        Ok(u32::try_from(shift)
+            .ok()
+            .and_then(|shift| self.a.checked_shr(shift))
+            .unwrap_or(0))
+    }
+
+    pub fn step(&mut self) -> Result<(), Fault> {
+        if self.ip + 1 >= self.program.len() {
+            return Ok(());
         }
-        let instruction = self.program[self.ip].into();
+        let instruction = Instruction::try_from(self.program[self.ip])
+            .map_err(|_| Fault::BadInstruction { ip: self.ip })?;
         let operand = self.program[self.ip + 1];
         let mut increment_ip = true;
         match instruction {
             Instruction::ADV => {
-                self.a >>= self.combo(operand);
+                self.a = self.shifted_a(operand)?;
             }
             Instruction::BXL => {
                 self.b ^= operand as usize;
             }
             Instruction::BST => {
-                self.b = self.combo(operand) % 8;
+                self.b = self.combo(operand)? % 8;
             }
             Instruction::JNZ => {
                 if self.a != 0 {
@@ -65,53 +88,68 @@ impl Computer {
                 self.b ^= self.c;
             }
             Instruction::OUT => {
-                self.output.push((self.combo(operand) % 8) as u8);
+                self.output.push((self.combo(operand)? % 8) as u8);
             }
             Instruction::BDV => {
-                self.b = self.a >> self.combo(operand);
+                self.b = self.shifted_a(operand)?;
             }
             Instruction::CDV => {
-                self.c = self.a >> self.combo(operand);
+                self.c = self.shifted_a(operand)?;
             }
         }
         if increment_ip {
             self.ip += 2;
         }
+        Ok(())
     }
 
-    pub fn run(&mut self) {
-        while self.ip < self.program.len() - 1 {
-            // dbg!(&self);
-            self.step();
-        }
-    }
-
-    pub fn try_run(&mut self) -> Result<(), &str> {
+    pub fn run(&mut self) -> Result<(), Fault> {
         let mut seen_states = HashSet::new();
-        while self.ip < self.program.len() - 1 {
+        while self.ip + 1 < self.program.len() {
             if !seen_states.insert((self.a, self.b, self.c, self.ip)) {
-                return Err("repeated state");
-            }
-            if !self.program.starts_with(&self.output) {
-                return Err("wrong output");
+                return Err(Fault::RepeatedState);
             }
-            // dbg!(&self);
-            self.step();
+            self.step()?;
         }
         Ok(())
     }
+
+}
+
+/// Why a [`Computer`] stopped before running off the end of its program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// The word at `ip` isn't an opcode.
+    BadInstruction { ip: usize },
+    /// The instruction at `ip` takes a combo operand, but its operand is the reserved 7.
+    ReservedOperand { ip: usize },
+    /// The program loops forever.
+    RepeatedState,
+}
+
+impl Display for Fault {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BadInstruction { ip } => write!(f, "no instruction at {ip}"),
+            Self::ReservedOperand { ip } => write!(f, "reserved combo operand 7 at {ip}"),
+            Self::RepeatedState => write!(f, "repeated state"),
+        }
+    }
 }
 
 impl Display for Computer {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for chunk in self.program.chunks(2) {
             let operand = match (chunk[0], chunk[1]) {
-                (0 | 2 | 5 | 6 | 7, 4 | 5 | 6) => {
+                (0 | 2 | 5..=7, 4..=6) => {
                     format!("{}", "ABC".chars().nth((chunk[1] - 4) as usize).unwrap())
                 }
                 _ => format!("{}", chunk[1]),
             };
-            writeln!(f, "{:?} {operand}", Instruction::from(chunk[0]))?;
+            match Instruction::try_from(chunk[0]) {
+                Ok(instruction) => writeln!(f, "{instruction:?} {operand}")?,
+                Err(_) => writeln!(f, "{} {operand}", chunk[0])?,
+            }
         }
         Ok(())
     }
@@ -130,9 +168,20 @@ enum Instruction {
     CDV,
 }
 
-impl From<u8> for Instruction {
-    fn from(value: u8) -> Self {
-        match value {
+impl Instruction {
+    fn takes_combo(self) -> bool {
+        matches!(
+            self,
+            Self::ADV | Self::BST | Self::OUT | Self::BDV | Self::CDV
+        )
+    }
+}
+
+impl TryFrom<u8> for Instruction {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
             0 => Self::ADV,
             1 => Self::BXL,
             2 => Self::BST,
@@ -141,69 +190,109 @@ impl From<u8> for Instruction {
             5 => Self::OUT,
             6 => Self::BDV,
             7 => Self::CDV,
-            _ => panic!("{value} too large for instruction"),
-        }
+            _ => return Err(value),
+        })
     }
 }
 
-fn parse(input: String) -> Computer {
-    let mut lines = input.lines();
-    let a = lines.next().unwrap()[11..].trim().parse().unwrap();
-    let b = lines.next().unwrap()[11..].trim().parse().unwrap();
-    let c = lines.next().unwrap()[11..].trim().parse().unwrap();
-    lines.next().unwrap();
-    let program = lines.next().unwrap()[8..]
-        .trim()
-        .split(',')
-        .map(|s| s.parse().unwrap())
-        .collect_vec();
-    Computer::new(a, b, c, program)
+/// What `computer` outputs from A = `a` if it halts within `steps` steps.
+fn output_for(computer: &Computer, a: usize, steps: usize) -> Option<Vec<u8>> {
+    let mut c = computer.clone();
+    c.a = a;
+    for _ in 0..steps {
+        if c.ip + 1 >= c.program.len() {
+            break;
+        }
+        c.step().ok()?;
+    }
+    (c.ip + 1 >= c.program.len()).then_some(c.output)
 }
 
-pub fn part1(input: String) -> String {
-    let mut c = parse(input);
-    // dbg!(&c);
-    c.run();
-    c.output.into_iter().map(|n| format!("{n}")).join(",")
+/// The lowest A that makes `computer` output its own program, extending `a`, which outputs the
+/// last `digits` numbers of it, by one octal digit at a time.
+fn quine_a(computer: &Computer, a: usize, digits: usize) -> Option<usize> {
+    let program = &computer.program;
+    let steps = program.len() * program.len();
+    (0..8).find_map(|d| {
+        let a = a.checked_mul(8)?.checked_add(d).filter(|&a| a != 0)?;
+        if output_for(computer, a, steps)? != program[program.len() - digits - 1..] {
+            return None;
+        }
+        if digits + 1 == program.len() {
+            Some(a)
+        } else {
+            quine_a(computer, a, digits + 1)
+        }
+    })
 }
 
-pub fn part2(input: String) -> u64 {
-    let mut c_orig = parse(input);
-    let mut candidate = 8usize.pow(15);
-    for n in 0..=14 {
-        let to_match = &c_orig.program[if n == 14 { 0.. } else { 15 - n.. }];
-        // dbg!(to_match);
-        loop {
-            // dbg!(candidate);
-            let mut c = c_orig.clone();
-            c.a = candidate;
-            for _ in 0..(8 * 16) {
-                c.step();
-            }
-            if c.output.len() != 16 {
-                continue;
+pub struct Day17;
+
+impl Solution for Day17 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 17;
+
+    type Config = ();
+    type Input = Computer;
+
+    fn parse(input: &str, _: &()) -> Result<Self::Input, ParseError> {
+        let p = Parser::new(input);
+        let mut lines = input.lines();
+        let mut register = |name: &str| -> Result<usize, ParseError> {
+            let line = p.next(&mut lines, input, &format!("register {name}"))?;
+            p.num(p.strip_prefix(line, &format!("Register {name}: "))?.trim())
+        };
+        let (a, b, c) = (register("A")?, register("B")?, register("C")?);
+        p.next(&mut lines, input, "a blank line")?;
+        let line = p.next(&mut lines, input, "a program")?;
+        let words = p.strip_prefix(line, "Program: ")?.trim();
+        let program: Vec<u8> = p.nums(words, ",")?;
+        if program.len() < 2 {
+            return Err(p.missing(words, "an instruction and its operand"));
+        }
+        // Jumps to odd addresses aren't checked here, so running can still fault.
+        let numbers = words.split(',').collect_vec();
+        for (i, (word, number)) in program.iter().zip(&numbers).enumerate() {
+            if *word > 7 {
+                return Err(p.error(number, format!("expected 0 to 7, found {word}")));
             }
-            if c.output.ends_with(&to_match) {
-                break;
+            let combo = Instruction::try_from(*word).is_ok_and(Instruction::takes_combo);
+            if i % 2 == 0 && combo && program.get(i + 1) == Some(&7) {
+                return Err(p.error(numbers[i + 1], "combo operand 7 is reserved"));
             }
-            candidate += 8usize.pow(14 - n as u32);
         }
+        Ok(Computer::new(a, b, c, program))
     }
-    c_orig.a = candidate;
-    c_orig.try_run().unwrap();
-
-    // println!("{:?}", c.program);
-    // println!("{c_orig}");
-    /*
-    while a != 0 {
-        b = a % 8;
-        b ^= 2;
-        c = a >> b;
-        b = 7 - b;
-        b ^= c;
-        a >>= 3;
-        print(b % 8);
+
+    fn part1(computer: &Self::Input) -> Answer {
+        let mut c = computer.clone();
+        match c.run() {
+            Ok(()) => Answer::from(c.output.into_iter().map(|n| format!("{n}")).join(",")),
+            Err(_) => Answer::None,
+        }
+    }
+
+    fn part2(computer: &Self::Input) -> Answer {
+        // The program outputs one octal digit of A per pass over its instructions, so A has as
+        // many digits as the program has numbers, and the highest digits decide the last outputs.
+        /*
+        while a != 0 {
+            b = a % 8;
+            b ^= 2;
+            c = a >> b;
+            b = 7 - b;
+            b ^= c;
+            a >>= 3;
+            print(b % 8);
+        }
+         */
+        Answer::from(quine_a(computer, 0, 0).map(|a| a as u64))
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![
+            Example::new(EXAMPLE_INPUT).part1("4,6,3,5,6,3,5,2,1,0"),
+            Example::new(EXAMPLE_INPUT_2).part2(117440),
+        ]
     }
-     */
-    candidate as u64
 }
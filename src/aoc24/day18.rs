@@ -1,24 +1,57 @@
-use itertools::Itertools;
 use pathfinding::{
     grid::Grid,
     prelude::{bfs_bidirectional, dijkstra},
 };
 
-pub fn parse(input: &[&str], size: usize) -> Grid {
-    let mut g = Grid::new(size, size);
-    input.iter().for_each(|l| {
-        g.add_vertex(
-            l.split(",")
-                .map(|s| s.parse::<usize>().unwrap())
-                .collect_tuple()
-                .unwrap(),
-        );
-    });
-    g.invert();
-    g
+use crate::{
+    solution::{Example, Solution},
+    util::{Answer, Coordinate, ParseError, Parser},
+};
+
+const EXAMPLE_INPUT: &str = r"5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0";
+
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    /// Width and height of the memory space.
+    pub size: usize,
+    /// How many bytes have fallen by the time part 1 starts.
+    pub blocks: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            size: 71,
+            blocks: 1024,
+        }
+    }
 }
 
-pub fn parse2(input: &[(usize, usize)], size: usize) -> Grid {
+pub fn build_grid(input: &[(usize, usize)], size: usize) -> Grid {
     let mut g = Grid::new(size, size);
     input.iter().for_each(|&p| {
         g.add_vertex(p);
@@ -33,43 +66,59 @@ pub fn process_block(g: &mut Grid, input: (usize, usize)) {
     g.invert();
 }
 
-pub fn part1(input: String) -> u64 {
-    const BLOCKS: usize = 1024;
-    const SIZE: usize = 71;
-    let g = parse(&input.lines().take(BLOCKS).collect_vec(), SIZE);
-    const START: (usize, usize) = (0, 0);
-    const GOAL: (usize, usize) = (SIZE - 1, SIZE - 1);
-    let sol = dijkstra(
-        &START,
-        |&p| g.neighbours(p).into_iter().map(|n| (n, 1)),
-        |&p| p == GOAL,
-    )
-    .unwrap();
+pub struct Day18;
 
-    sol.1 as u64
-}
+impl Solution for Day18 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 18;
 
-pub fn part2(input: String) -> String {
-    const SIZE: usize = 71;
-    let coords = input
-        .lines()
-        .map(|l| {
-            l.split(",")
-                .map(|s| s.parse::<usize>().unwrap())
-                .collect_tuple::<(_, _)>()
-                .unwrap()
-        })
-        .collect_vec();
-    let mut g = Grid::new(SIZE, SIZE);
-    g.invert();
-    for block in 0.. {
-        process_block(&mut g, coords[block]);
+    type Config = Config;
+    type Input = (Vec<(usize, usize)>, Config);
+
+    fn parse(input: &str, config: &Config) -> Result<Self::Input, ParseError> {
+        let p = Parser::new(input);
+        let coords = input
+            .lines()
+            .map(|l| p.pair(l, ","))
+            .collect::<Result<_, _>>()?;
+        Ok((coords, *config))
+    }
+
+    fn part1((coords, config): &Self::Input) -> Answer {
+        let g = build_grid(&coords[..config.blocks.min(coords.len())], config.size);
         const START: (usize, usize) = (0, 0);
-        const GOAL: (usize, usize) = (SIZE - 1, SIZE - 1);
-        let successors = |p: &(usize, usize)| g.neighbours(*p).into_iter();
-        if let None = bfs_bidirectional(&START, &GOAL, successors, successors) {
-            return format!("{},{}", coords[block].0, coords[block].1);
+        let goal = (config.size - 1, config.size - 1);
+        let sol = dijkstra(
+            &START,
+            |&p| g.neighbours(p).into_iter().map(|n| (n, 1)),
+            |&p| p == goal,
+        );
+
+        Answer::from(sol.map(|(_, cost)| cost as u64))
+    }
+
+    fn part2((coords, config): &Self::Input) -> Answer {
+        let mut g = Grid::new(config.size, config.size);
+        g.invert();
+        for &block in coords {
+            process_block(&mut g, block);
+            const START: (usize, usize) = (0, 0);
+            let goal = (config.size - 1, config.size - 1);
+            let successors = |p: &(usize, usize)| g.neighbours(*p).into_iter();
+            if bfs_bidirectional(&START, &goal, successors, successors).is_none() {
+                return Coordinate::from_pathfinding(block).into();
+            }
         }
+        Answer::None
+    }
+
+    fn examples() -> Vec<Example<Config>> {
+        vec![Example::new(EXAMPLE_INPUT)
+            .config(Config {
+                size: 7,
+                blocks: 12,
+            })
+            .part1(22)
+            .part2("6,1")]
     }
-    "".to_owned()
 }
@@ -1,32 +1,22 @@
-use std::{
-    collections::HashSet,
-    sync::{Arc, Mutex},
-};
-
 use cached::proc_macro::cached;
 use itertools::Itertools;
-use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
-
-fn parse(input: String) -> (Vec<String>, Vec<String>) {
-    let mut lines = input.lines();
-    let avail = lines
-        .next()
-        .unwrap()
-        .split(", ")
-        .map(String::from)
-        .collect_vec();
-    lines.next().unwrap();
-    let goals = lines.map(String::from).collect_vec();
-    (avail, goals)
-}
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
-pub fn part1(input: String) -> u64 {
-    let (avail, goals) = parse(input);
-    goals
-        .into_par_iter()
-        .filter(|g| can_make_string(g.clone(), avail.clone()) > 0)
-        .count() as u64
-}
+use crate::{
+    solution::{Example, Solution},
+    util::{Answer, ParseError, Parser},
+};
+
+const EXAMPLE_INPUT: &str = r"r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb";
 
 #[cached]
 fn can_make_string(goal: String, avail: Vec<String>) -> u64 {
@@ -42,10 +32,47 @@ fn can_make_string(goal: String, avail: Vec<String>) -> u64 {
         .sum()
 }
 
-pub fn part2(input: String) -> u64 {
-    let (avail, goals) = parse(input);
-    goals
-        .into_par_iter()
-        .map(|g| can_make_string(g, avail.clone()))
-        .sum::<u64>()
+pub struct Day19;
+
+impl Solution for Day19 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 19;
+
+    type Config = ();
+    type Input = (Vec<String>, Vec<String>);
+
+    fn parse(input: &str, _: &()) -> Result<Self::Input, ParseError> {
+        let p = Parser::new(input);
+        let mut lines = input.lines();
+        let avail = p
+            .next(&mut lines, input, "the available towels")?
+            .split(", ")
+            .map(String::from)
+            .collect_vec();
+        p.next(&mut lines, input, "a blank line")?;
+        let goals = lines.map(String::from).collect_vec();
+        Ok((avail, goals))
+    }
+
+    fn part1((avail, goals): &Self::Input) -> Answer {
+        Answer::from(
+            goals
+                .par_iter()
+                .filter(|&g| can_make_string(g.clone(), avail.clone()) > 0)
+                .count() as u64,
+        )
+    }
+
+    fn part2((avail, goals): &Self::Input) -> Answer {
+        Answer::from(
+            goals
+                .par_iter()
+                .map(|g| can_make_string(g.clone(), avail.clone()))
+                .sum::<u64>(),
+        )
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(6).part2(16)]
+    }
 }
@@ -1,30 +1,39 @@
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::HashSet;
 
-use crate::util::{iter::IteratorExt, ParallelIteratorExt};
-use itertools::Itertools;
-use pathfinding::{
-    grid::Grid,
-    matrix::Matrix,
-    prelude::{astar_bag, dijkstra},
-};
+use pathfinding::{grid::Grid, prelude::dijkstra};
 use rayon::prelude::*;
 
-fn parse(input: String) -> (Grid, (usize, usize), (usize, usize)) {
-    let m = Matrix::from_rows(input.lines().map(|l| l.bytes())).unwrap();
-    let start_pos = m
-        .items()
-        .find_map(|(p, &c)| (c == b'S').then_some(p))
-        .unwrap();
-    let end_pos = m
-        .items()
-        .find_map(|(p, &c)| (c == b'E').then_some(p))
-        .unwrap();
+use crate::{
+    solution::{Example, Solution},
+    util::{pathfinding_grid, Answer, Coordinate, ParallelIteratorExt, ParseError, Parser},
+};
+
+const EXAMPLE_INPUT: &str = r"###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############";
 
-    (
-        Grid::from(m.map(|c| c != b'#')),
-        (start_pos.1, start_pos.0),
-        (end_pos.1, end_pos.0),
-    )
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    /// Only cheats saving at least this many picoseconds are counted.
+    pub min_save: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self { min_save: 100 }
+    }
 }
 
 fn grid_successors(
@@ -34,69 +43,92 @@ fn grid_successors(
     grid.neighbours(p).into_iter().map(|n| (n, 1))
 }
 
-pub fn part1(input: String) -> u64 {
-    let (grid, start, end) = parse(input);
-    let (path, legit_time) =
-        dijkstra(&start, |&p| grid_successors(&grid, p), |&p| p == end).unwrap();
-    let mut neg_grid = grid.clone();
-    neg_grid.invert();
-    let path_adj: HashSet<_> = path
-        .into_par_iter()
-        .flat_map(|vertex| {
-            let (x, y) = vertex;
-            let mut candidates = Vec::with_capacity(4);
-            if x > 0 {
-                candidates.push((x - 1, y));
-            }
-            if x + 1 < neg_grid.width {
-                candidates.push((x + 1, y));
-            }
-            if y > 0 {
-                candidates.push((x, y - 1));
-            }
-            if y + 1 < neg_grid.height {
-                candidates.push((x, y + 1));
-            }
-            candidates.retain(|&v| neg_grid.has_vertex(v));
-            candidates
-        })
-        .duplicates()
-        .collect();
-    let timesaves = path_adj
-        .into_par_iter()
-        .filter(|&p| {
-            let mut g = grid.clone();
-            g.add_vertex(p);
-            let new_time = dijkstra(&start, |&p| grid_successors(&g, p), |&p| p == end)
-                .unwrap()
-                .1;
-            legit_time - new_time >= 100
-        })
-        .count();
-    timesaves as u64
-}
+pub struct Day20;
+
+impl Solution for Day20 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 20;
+
+    type Config = Config;
+    type Input = (Grid, (usize, usize), (usize, usize), Config);
+
+    fn parse(input: &str, config: &Config) -> Result<Self::Input, ParseError> {
+        let p = Parser::new(input);
+        let map = p.grid(input)?;
+        let start_pos = p.find(input, &map, b'S')?;
+        let end_pos = p.find(input, &map, b'E')?;
+        Ok((
+            pathfinding_grid(&map, |&c| c != b'#'),
+            Coordinate::from(start_pos).to_pathfinding(),
+            Coordinate::from(end_pos).to_pathfinding(),
+            *config,
+        ))
+    }
+
+    fn part1((grid, start, end, config): &Self::Input) -> Answer {
+        let Some((path, legit_time)) =
+            dijkstra(start, |&p| grid_successors(grid, p), |&p| p == *end)
+        else {
+            return Answer::None;
+        };
+        let mut neg_grid = grid.clone();
+        neg_grid.invert();
+        let path_adj: HashSet<_> = path
+            .into_par_iter()
+            .flat_map(|vertex| {
+                Coordinate::from_pathfinding(vertex)
+                    .neighbors4()
+                    .map(Coordinate::to_pathfinding)
+                    .filter(|&v| neg_grid.has_vertex(v))
+                    .collect::<Vec<_>>()
+            })
+            .duplicates()
+            .collect();
+        let timesaves = path_adj
+            .into_par_iter()
+            .filter(|&p| {
+                let mut g = grid.clone();
+                g.add_vertex(p);
+                let new_time = dijkstra(start, |&p| grid_successors(&g, p), |&p| p == *end)
+                    .unwrap()
+                    .1;
+                legit_time - new_time >= config.min_save
+            })
+            .count();
+        Answer::from(timesaves as u64)
+    }
+
+    fn part2((grid, start, end, config): &Self::Input) -> Answer {
+        let Some((path, _)) = dijkstra(start, |&p| grid_successors(grid, p), |&p| p == *end) else {
+            return Answer::None;
+        };
 
-pub fn part2(input: String) -> u64 {
-    let (grid, start, end) = parse(input);
-    let path = dijkstra(&start, |&p| grid_successors(&grid, p), |&p| p == end)
-        .unwrap()
-        .0;
+        let min_save = config.min_save;
+        let timesaves = (0..path.len().saturating_sub(min_save))
+            .into_par_iter()
+            .flat_map(|i| {
+                (i + min_save..path.len())
+                    .into_par_iter()
+                    .map(move |j| (i, j))
+            })
+            .filter_map(|(i, j)| {
+                let dist = Coordinate::from_pathfinding(path[i])
+                    .manhattan(Coordinate::from_pathfinding(path[j]));
+                (dist <= 20 && j - i - dist >= min_save).then_some(j - i - dist)
+            })
+            .count();
 
-    const MIN_SAVE: usize = 100;
-    let timesaves = (0..path.len() - MIN_SAVE)
-        .into_par_iter()
-        .flat_map(|i| {
-            (i + MIN_SAVE..path.len())
-                .into_par_iter()
-                .map(move |j| (i, j))
-        })
-        .filter_map(|(i, j)| {
-            let a = path[i];
-            let b = path[j];
-            let dist = a.0.abs_diff(b.0) + a.1.abs_diff(b.1);
-            (dist <= 20 && j - i - dist >= MIN_SAVE).then_some(j - i - dist)
-        })
-        .count();
+        Answer::from(timesaves as u64)
+    }
 
-    timesaves as u64
+    fn examples() -> Vec<Example<Config>> {
+        vec![
+            Example::new(EXAMPLE_INPUT)
+                .config(Config { min_save: 20 })
+                .part1(5),
+            Example::new(EXAMPLE_INPUT)
+                .config(Config { min_save: 50 })
+                .part2(285),
+        ]
+    }
 }
@@ -6,7 +6,16 @@ use pathfinding::{grid::Grid, matrix::Matrix, prelude::astar_bag};
 use rayon::prelude::*;
 use regex::Regex;
 
-use crate::util::Direction;
+use crate::{
+    solution::{Example, Solution},
+    util::{Answer, Coordinate, Direction, ParseError, Parser},
+};
+
+const EXAMPLE_INPUT: &str = r"029A
+980A
+179A
+456A
+379A";
 
 static NUMPAD_MATRIX: LazyLock<Matrix<Option<u8>>> = LazyLock::new(|| {
     Matrix::from_vec(
@@ -85,7 +94,11 @@ fn find_shortest_numpad_paths(from: (usize, usize), to: (usize, usize)) -> Arc<V
             .map(|w| {
                 let a = w[0];
                 let b = w[1];
-                Direction::from_coords((a.1, a.0).into(), (b.1, b.0).into()).to_char()
+                Direction::from_coords(
+                    Coordinate::from_pathfinding(a),
+                    Coordinate::from_pathfinding(b),
+                )
+                .to_char()
             })
             .chain([b'A'])
             .collect_vec()
@@ -109,7 +122,11 @@ fn find_shortest_dpad_paths(from: (usize, usize), to: (usize, usize)) -> Arc<Vec
             .map(|w| {
                 let a = w[0];
                 let b = w[1];
-                Direction::from_coords((a.1, a.0).into(), (b.1, b.0).into()).to_char()
+                Direction::from_coords(
+                    Coordinate::from_pathfinding(a),
+                    Coordinate::from_pathfinding(b),
+                )
+                .to_char()
             })
             .chain([b'A'])
             .collect_vec()
@@ -164,38 +181,51 @@ fn find_shortest_seq(s: Vec<u8>, depth: usize, top: bool) -> usize {
         .1
 }
 
-pub fn part1(input: String) -> u64 {
-    let num_regex = Regex::new(r"\d+").unwrap();
-    input
-        .lines()
-        .map(|line| {
-            let numeric_part = num_regex
-                .find(line)
-                .unwrap()
-                .as_str()
-                .parse::<usize>()
-                .unwrap();
-
-            let path_len = find_shortest_seq(line.bytes().collect_vec(), 2, true);
-            numeric_part * path_len
-        })
-        .sum::<usize>() as u64
-}
+pub struct Day21;
+
+impl Solution for Day21 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 21;
+
+    type Config = ();
+    type Input = Vec<(usize, Vec<u8>)>;
+
+    fn parse(input: &str, _: &()) -> Result<Self::Input, ParseError> {
+        let p = Parser::new(input);
+        let num_regex = Regex::new(r"\d+").unwrap();
+        input
+            .lines()
+            .map(|line| {
+                p.only(line, b"0123456789A", "a keypad button")?;
+                let numeric_part = num_regex
+                    .find(line)
+                    .ok_or_else(|| p.error(line, "expected a number"))?;
+                Ok((p.num(numeric_part.as_str())?, line.bytes().collect_vec()))
+            })
+            .collect()
+    }
 
-pub fn part2(input: String) -> u64 {
-    let num_regex = Regex::new(r"\d+").unwrap();
-    input
-        .lines()
-        .map(|line| {
-            let numeric_part = num_regex
-                .find(line)
-                .unwrap()
-                .as_str()
-                .parse::<usize>()
-                .unwrap();
-
-            let path_len = find_shortest_seq(line.bytes().collect_vec(), 25, true);
-            numeric_part * path_len
-        })
-        .sum::<usize>() as u64
+    fn part1(codes: &Self::Input) -> Answer {
+        Answer::from(
+            codes
+                .iter()
+                .map(|(numeric_part, code)| numeric_part * find_shortest_seq(code.clone(), 2, true))
+                .sum::<usize>() as u64,
+        )
+    }
+
+    fn part2(codes: &Self::Input) -> Answer {
+        Answer::from(
+            codes
+                .iter()
+                .map(|(numeric_part, code)| {
+                    numeric_part * find_shortest_seq(code.clone(), 25, true)
+                })
+                .sum::<usize>() as u64,
+        )
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(126384)]
+    }
 }
@@ -3,6 +3,21 @@ use std::collections::HashSet;
 use itertools::Itertools;
 use rayon::prelude::*;
 
+use crate::{
+    solution::{Example, Solution},
+    util::{Answer, ParseError, Parser},
+};
+
+const EXAMPLE_INPUT: &str = r"1
+10
+100
+2024";
+
+const EXAMPLE_INPUT_2: &str = r"1
+2
+3
+2024";
+
 fn step_secret(secret: u64) -> u64 {
     let mut res = ((secret * 64) ^ secret) % 16777216;
     res ^= res / 32;
@@ -12,64 +27,93 @@ fn step_secret(secret: u64) -> u64 {
     res
 }
 
-pub fn part1(input: String) -> u64 {
-    input
-        .lines()
-        .map(|l| {
-            let mut secret = l.parse().unwrap();
-            for _ in 0..2000 {
-                secret = step_secret(secret);
-            }
-            secret
-        })
-        .sum()
-}
+pub struct Day22;
 
-pub fn part2(input: String) -> u64 {
-    let mut initial_prices = vec![];
-    let deltas = input
-        .lines()
-        .map(|l| {
-            let mut secret = l.parse().unwrap();
-            let mut result = vec![];
-            for i in 0..2000 {
-                secret = step_secret(secret);
-                if i == 0 {
-                    initial_prices.push(secret % 10);
-                }
-                result.push(secret % 10);
-            }
-            result
-                .windows(2)
-                .map(|x| {
-                    let a = x[0];
-                    let b = x[1];
-                    b - a
+impl Solution for Day22 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 22;
+
+    type Config = ();
+    type Input = Vec<u64>;
+
+    fn parse(input: &str, _: &()) -> Result<Self::Input, ParseError> {
+        let p = Parser::new(input);
+        if input.trim().is_empty() {
+            return Err(p.error(input, "expected a secret number"));
+        }
+        input.lines().map(|l| p.num(l)).collect()
+    }
+
+    fn part1(secrets: &Self::Input) -> Answer {
+        Answer::from(
+            secrets
+                .iter()
+                .map(|&secret| {
+                    let mut secret = secret;
+                    for _ in 0..2000 {
+                        secret = step_secret(secret);
+                    }
+                    secret
                 })
-                .collect_vec()
-        })
-        .collect_vec();
+                .sum::<u64>(),
+        )
+    }
 
-    deltas
-        .iter()
-        .flat_map(|ds| ds.windows(4))
-        .collect::<HashSet<_>>()
-        .into_par_iter()
-        .map(|w| {
-            let mut profit = 0;
-            for (buyer_deltas, &init_price) in deltas.iter().zip(initial_prices.iter()) {
-                let mut pos = 0;
-                while pos < buyer_deltas.len() - 4 && buyer_deltas[pos..pos + 4] != *w {
-                    pos += 1;
-                }
-                if pos < buyer_deltas.len() - 4 {
-                    profit += buyer_deltas[..pos + 4]
-                        .iter()
-                        .fold(init_price, |a, v| a + v);
+    fn part2(secrets: &Self::Input) -> Answer {
+        let mut initial_prices = vec![];
+        let deltas = secrets
+            .iter()
+            .map(|&secret| {
+                let mut secret = secret;
+                let mut result = vec![];
+                for i in 0..2000 {
+                    secret = step_secret(secret);
+                    if i == 0 {
+                        initial_prices.push(secret % 10);
+                    }
+                    result.push(secret % 10);
                 }
-            }
-            profit
-        })
-        .max()
-        .unwrap()
+                result
+                    .windows(2)
+                    .map(|x| {
+                        let a = x[0];
+                        let b = x[1];
+                        b.wrapping_sub(a)
+                    })
+                    .collect_vec()
+            })
+            .collect_vec();
+
+        Answer::from(
+            deltas
+                .iter()
+                .flat_map(|ds| ds.windows(4))
+                .collect::<HashSet<_>>()
+                .into_par_iter()
+                .map(|w| {
+                    let mut profit = 0;
+                    for (buyer_deltas, &init_price) in deltas.iter().zip(initial_prices.iter()) {
+                        let mut pos = 0;
+                        while pos < buyer_deltas.len() - 4 && buyer_deltas[pos..pos + 4] != *w {
+                            pos += 1;
+                        }
+                        if pos < buyer_deltas.len() - 4 {
+                            profit += buyer_deltas[..pos + 4]
+                                .iter()
+                                .fold(init_price, |a, v| a.wrapping_add(*v));
+                        }
+                    }
+                    profit
+                })
+                .max()
+                .unwrap(),
+        )
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![
+            Example::new(EXAMPLE_INPUT).part1(37327623),
+            Example::new(EXAMPLE_INPUT_2).part2(23),
+        ]
+    }
 }
@@ -1,64 +1,119 @@
 use rayon::prelude::*;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
 use itertools::Itertools;
-use petgraph::{
-    algo::{connected_components, tarjan_scc},
-    dot::Dot,
-    prelude::*,
+use petgraph::prelude::*;
+
+use crate::{
+    solution::{Example, Solution},
+    util::{Answer, ParseError, Parser},
 };
 
-fn parse(input: String) -> UnGraph<String, ()> {
-    let cnxs = input.lines().map(|l| l.split("-"));
-    let mut g = UnGraph::default();
-    let mut nodes = HashMap::new();
-    for mut cnx in cnxs {
-        let a = cnx.next().unwrap().to_owned();
-        let b = cnx.next().unwrap().to_owned();
-        let an = *nodes.entry(a.clone()).or_insert_with(|| g.add_node(a));
-        let bn = *nodes.entry(b.clone()).or_insert_with(|| g.add_node(b));
-        g.add_edge(an, bn, ());
+const EXAMPLE_INPUT: &str = r"kh-tc
+qp-kh
+de-cg
+ka-co
+yn-aq
+qp-ub
+cg-tb
+vc-aq
+tb-ka
+wh-tc
+yn-cg
+kh-ub
+ta-co
+de-co
+tc-td
+tb-wq
+wh-td
+ta-ka
+td-qp
+aq-cg
+wq-ub
+ub-vc
+de-ta
+wq-aq
+wq-vc
+wh-yn
+ka-de
+kh-ta
+co-tc
+wh-qp
+tb-vc
+td-yn";
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 23;
+
+    type Config = ();
+    type Input = UnGraph<String, ()>;
+
+    fn parse(input: &str, _: &()) -> Result<Self::Input, ParseError> {
+        let p = Parser::new(input);
+        let mut g = UnGraph::default();
+        let mut nodes = HashMap::new();
+        if input.trim().is_empty() {
+            return Err(p.error(input, "expected a connection"));
+        }
+        for line in input.lines() {
+            let (a, b) = p.split_once(line, "-")?;
+            let an = *nodes
+                .entry(a.to_owned())
+                .or_insert_with(|| g.add_node(a.to_owned()));
+            let bn = *nodes
+                .entry(b.to_owned())
+                .or_insert_with(|| g.add_node(b.to_owned()));
+            g.add_edge(an, bn, ());
+        }
+        Ok(g)
     }
-    g
-}
 
-pub fn part1(input: String) -> u64 {
-    let g = parse(input);
-    // println!("{:?}", Dot::with_config(&g, &[]));
-    g.node_indices()
-        .tuple_combinations()
-        .filter(|&(a, b, c)| {
-            g.contains_edge(a, b)
-                && g.contains_edge(c, b)
-                && g.contains_edge(a, c)
-                && (g[a].starts_with("t") || g[b].starts_with("t") || g[c].starts_with("t"))
-        })
-        .count() as u64
-}
+    fn part1(g: &Self::Input) -> Answer {
+        Answer::from(
+            g.node_indices()
+                .tuple_combinations()
+                .filter(|&(a, b, c)| {
+                    g.contains_edge(a, b)
+                        && g.contains_edge(c, b)
+                        && g.contains_edge(a, c)
+                        && (g[a].starts_with("t") || g[b].starts_with("t") || g[c].starts_with("t"))
+                })
+                .count() as u64,
+        )
+    }
 
-pub fn part2(input: String) -> String {
-    let g = parse(input);
-    g.node_indices()
-        .par_bridge()
-        .filter_map(|i| {
-            let neighbors = g.neighbors(i).chain([i]).collect_vec();
-            (0..neighbors.len()).find_map(|k| {
-                neighbors
-                    .iter()
-                    .copied()
-                    .combinations(neighbors.len() - k)
-                    .find(|n| {
-                        n.iter()
+    fn part2(g: &Self::Input) -> Answer {
+        Answer::from(
+            g.node_indices()
+                .par_bridge()
+                .filter_map(|i| {
+                    let neighbors = g.neighbors(i).chain([i]).collect_vec();
+                    (0..neighbors.len()).find_map(|k| {
+                        neighbors
+                            .iter()
                             .copied()
-                            .tuple_combinations()
-                            .all(|(a, b)| g.contains_edge(a, b))
+                            .combinations(neighbors.len() - k)
+                            .find(|n| {
+                                n.iter()
+                                    .copied()
+                                    .tuple_combinations()
+                                    .all(|(a, b)| g.contains_edge(a, b))
+                            })
                     })
-            })
-        })
-        .max_by_key(|n| n.len())
-        .unwrap()
-        .into_iter()
-        .map(|i| &g[i])
-        .sorted()
-        .join(",")
+                })
+                .max_by_key(|n| n.len())
+                .unwrap()
+                .into_iter()
+                .map(|i| &g[i])
+                .sorted()
+                .join(","),
+        )
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(7).part2("co,de,ka,ta")]
+    }
 }
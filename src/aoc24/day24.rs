@@ -1,13 +1,25 @@
-use std::{
-    collections::{HashMap, HashSet},
-    convert::identity,
-};
+use std::collections::HashMap;
 
 use itertools::Itertools;
-use rayon::prelude::*;
+
+use crate::{
+    solution::{Example, Solution},
+    util::{Answer, ParseError, Parser},
+};
+
+const EXAMPLE_INPUT: &str = r"x00: 1
+x01: 1
+x02: 1
+y00: 0
+y01: 1
+y02: 0
+
+x00 AND y00 -> z00
+x01 XOR y01 -> z01
+x02 OR y02 -> z02";
 
 #[derive(Debug, Clone)]
-enum Instruction {
+pub enum Instruction {
     AND(String, String),
     OR(String, String),
     XOR(String, String),
@@ -15,24 +27,15 @@ enum Instruction {
 
 impl Instruction {
     fn is_and(&self) -> bool {
-        match self {
-            Self::AND(_, _) => true,
-            _ => false,
-        }
+        matches!(self, Self::AND(_, _))
     }
 
     fn is_or(&self) -> bool {
-        match self {
-            Self::OR(_, _) => true,
-            _ => false,
-        }
+        matches!(self, Self::OR(_, _))
     }
 
     fn is_xor(&self) -> bool {
-        match self {
-            Self::XOR(_, _) => true,
-            _ => false,
-        }
+        matches!(self, Self::XOR(_, _))
     }
 
     fn operands(&self) -> (&String, &String) {
@@ -80,7 +83,7 @@ fn resolve(values: &mut HashMap<String, Option<bool>>, cnxs: &HashMap<String, In
         for wire in values.keys().by_ref() {
             let value = values[wire];
             if value.is_none() {
-                if let Some(val) = cnxs[wire].try_resolve(&values) {
+                if let Some(val) = cnxs[wire].try_resolve(values) {
                     updates.insert(wire.to_owned(), val);
                 }
             }
@@ -94,57 +97,17 @@ fn resolve(values: &mut HashMap<String, Option<bool>>, cnxs: &HashMap<String, In
     }
 }
 
-fn parse(input: String) -> (HashMap<String, Option<bool>>, HashMap<String, Instruction>) {
-    // :wires:
-    let mut wires = HashMap::new();
-    let mut res = HashMap::new();
-    let (wires_list, ops) = input.split("\n\n").collect_tuple().unwrap();
-    for wire in wires_list.lines() {
-        let (name, val) = wire.split(": ").collect_tuple().unwrap();
-        wires.insert(name.to_owned(), Some(val == "1"));
-    }
-
-    for op_line in ops.lines() {
-        let (op_str, to) = op_line.split(" -> ").collect_tuple().unwrap();
-        let (a_name, op_name, b_name) = op_str.split(" ").collect_tuple().unwrap();
-        wires.entry(a_name.to_owned()).or_insert(None);
-        wires.entry(b_name.to_owned()).or_insert(None);
-        let instr = match op_name {
-            "AND" => Instruction::AND(a_name.to_owned(), b_name.to_owned()),
-            "OR" => Instruction::OR(a_name.to_owned(), b_name.to_owned()),
-            "XOR" => Instruction::XOR(a_name.to_owned(), b_name.to_owned()),
-            _ => unimplemented!(),
-        };
-        wires.entry(to.to_owned()).or_insert(None);
-        res.insert(to.to_owned(), instr);
-    }
-
-    (wires, res)
-}
-
-pub fn part1(input: String) -> u64 {
-    let (mut wires, cnxs) = parse(input);
-    resolve(&mut wires, &cnxs);
-    // dbg!(&wires);
-    // 0
-    wires
-        .into_iter()
-        .filter(|(k, _)| k.starts_with("z"))
-        .sorted_by_cached_key(|(k, _)| k.clone())
-        .rev()
-        .map(|(_, v)| v.unwrap())
-        .fold(0, |a, v| (a << 1) + v.then_some(1).unwrap_or_default())
-}
-
-fn validate(k: &String, i: &Instruction, cnxs: &HashMap<String, Instruction>) -> bool {
-    if k.starts_with("z") && k != "z45" {
+/// Checks the gate writing `k` is wired the way a ripple-carry adder would be. `carry` is the
+/// highest output wire, which holds the final carry instead of a sum bit.
+fn validate(k: &str, i: &Instruction, cnxs: &HashMap<String, Instruction>, carry: &str) -> bool {
+    if k.starts_with("z") && k != carry {
         return i.is_xor();
     }
 
     let (a, b) = i.operands();
-    if !k.starts_with("z")
-        && !((a.starts_with("x") && b.starts_with("y"))
-            || (a.starts_with("y") && b.starts_with("x")))
+    if !(k.starts_with("z")
+        || (a.starts_with("x") && b.starts_with("y"))
+        || (a.starts_with("y") && b.starts_with("x")))
     {
         return i.is_and() || i.is_or();
     }
@@ -172,18 +135,101 @@ fn validate(k: &String, i: &Instruction, cnxs: &HashMap<String, Instruction>) ->
     true
 }
 
-pub fn part2(input: String) -> String {
-    let (_, cnxs) = parse(input);
-
-    cnxs.iter()
-        .filter(|&(k, i)| {
-            let invalid = !validate(k, i, &cnxs);
-            // if invalid {
-            //     dbg!(k, i);
-            // }
-            invalid
-        })
-        .map(|(k, _)| k.clone())
-        .sorted()
-        .join(",")
+pub struct Day24;
+
+impl Solution for Day24 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 24;
+
+    type Config = ();
+    type Input = (HashMap<String, Option<bool>>, HashMap<String, Instruction>);
+
+    fn parse(input: &str, _: &()) -> Result<Self::Input, ParseError> {
+        let p = Parser::new(input);
+        // :wires:
+        let mut wires = HashMap::new();
+        let mut res = HashMap::new();
+        let (wires_list, ops) = p.split_once(input, "\n\n")?;
+        for wire in wires_list.lines() {
+            let (name, val) = p.split_once(wire, ": ")?;
+            let val = match val {
+                "0" => false,
+                "1" => true,
+                _ => return Err(p.error(val, format!("expected 0 or 1, found `{val}`"))),
+            };
+            wires.insert(name.to_owned(), Some(val));
+        }
+
+        let mut inputs = Vec::new();
+
+        for op_line in ops.lines() {
+            let (op_str, to) = p.split_once(op_line, " -> ")?;
+            let mut words = op_str.split(' ');
+            let a_name = p.next(&mut words, op_str, "a wire")?;
+            let op_name = p.next(&mut words, op_str, "a gate")?;
+            let b_name = p.next(&mut words, op_str, "a second wire")?;
+            wires.entry(a_name.to_owned()).or_insert(None);
+            wires.entry(b_name.to_owned()).or_insert(None);
+            inputs.extend([a_name, b_name]);
+            let instr = match op_name {
+                "AND" => Instruction::AND(a_name.to_owned(), b_name.to_owned()),
+                "OR" => Instruction::OR(a_name.to_owned(), b_name.to_owned()),
+                "XOR" => Instruction::XOR(a_name.to_owned(), b_name.to_owned()),
+                _ => return Err(p.error(op_name, format!("unknown gate `{op_name}`"))),
+            };
+            wires.entry(to.to_owned()).or_insert(None);
+            res.insert(to.to_owned(), instr);
+        }
+
+        for wire in inputs {
+            if wires[wire].is_none() && !res.contains_key(wire) {
+                return Err(p.error(wire, format!("no value or gate for wire `{wire}`")));
+            }
+        }
+        if !res.keys().any(|k| k.starts_with("z")) {
+            return Err(p.error(ops, "expected a gate writing a `z` wire"));
+        }
+        // With every input defined, a wire can only stay unresolved by feeding back into itself.
+        let mut resolved = wires.clone();
+        resolve(&mut resolved, &res);
+        let stuck = resolved.iter().filter(|(_, v)| v.is_none()).map(|(k, _)| k).min();
+        if let Some(wire) = stuck {
+            let gate = ops
+                .lines()
+                .find(|l| l.ends_with(&format!(" -> {wire}")))
+                .unwrap_or(ops);
+            return Err(p.error(gate, format!("wire `{wire}` depends on itself")));
+        }
+
+        Ok((wires, res))
+    }
+
+    fn part1((wires, cnxs): &Self::Input) -> Answer {
+        let mut wires = wires.clone();
+        resolve(&mut wires, cnxs);
+        Answer::from(
+            wires
+                .into_iter()
+                .filter(|(k, _)| k.starts_with("z"))
+                .sorted_by_cached_key(|(k, _)| k.clone())
+                .rev()
+                .map(|(_, v)| v.unwrap())
+                .fold(0u64, |a, v| (a << 1) + u64::from(v)),
+        )
+    }
+
+    fn part2((_, cnxs): &Self::Input) -> Answer {
+        let carry = cnxs.keys().filter(|k| k.starts_with("z")).max().unwrap();
+        Answer::from(
+            cnxs.iter()
+                .filter(|&(k, i)| !validate(k, i, cnxs, carry))
+                .map(|(k, _)| k.clone())
+                .sorted()
+                .join(","),
+        )
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(4)]
+    }
 }
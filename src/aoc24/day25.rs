@@ -1,43 +1,99 @@
-use grid::Grid;
 use itertools::Itertools;
 
-fn parse(input: String) -> (Vec<Vec<usize>>, Vec<Vec<usize>>, usize) {
-    let mut keys = vec![];
-    let mut locks = vec![];
-    let mut height = 0;
-
-    input.split("\n\n").for_each(|d| {
-        let first_line = d.lines().next().unwrap();
-        let is_lock = first_line.bytes().all(|b| b == b'#');
-        let mut d2 = Grid::from_vec(
-            d.lines().flat_map(|l| l.bytes()).collect_vec(),
-            first_line.len(),
-        );
-        d2.transpose();
-        height = d2.rows();
-        let nums = d2
-            .iter_rows()
-            .map(|r| r.filter(|&&b| b == b'#').count() - 1)
-            .collect_vec();
-        if is_lock {
-            locks.push(nums);
-        } else {
-            keys.push(nums);
+use crate::{
+    solution::{Example, Solution},
+    util::{Answer, ParseError, Parser},
+};
+
+const EXAMPLE_INPUT: &str = r"#####
+.####
+.####
+.####
+.#.#.
+.#...
+.....
+
+#####
+##.##
+.#.##
+...##
+...#.
+...#.
+.....
+
+.....
+#....
+#....
+#...#
+#.#.#
+#.###
+#####
+
+.....
+.....
+#.#..
+###..
+###.#
+###.#
+#####
+
+.....
+.....
+.....
+#....
+#.#..
+#.#.#
+#####";
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 25;
+
+    type Config = ();
+    type Input = (Vec<Vec<usize>>, Vec<Vec<usize>>, usize);
+
+    fn parse(input: &str, _: &()) -> Result<Self::Input, ParseError> {
+        let p = Parser::new(input);
+        let mut keys = vec![];
+        let mut locks = vec![];
+        let mut height = 0;
+
+        for d in input.split("\n\n") {
+            let mut d2 = p.grid(d)?;
+            let is_lock = d2.iter_row(0).all(|&b| b == b'#');
+            d2.transpose();
+            height = d2.rows();
+            let nums = d2
+                .iter_rows()
+                .map(|r| r.filter(|&&b| b == b'#').count().saturating_sub(1))
+                .collect_vec();
+            if is_lock {
+                locks.push(nums);
+            } else {
+                keys.push(nums);
+            }
         }
-    });
 
-    (locks, keys, height)
-}
+        Ok((locks, keys, height))
+    }
 
-pub fn part1(input: String) -> u64 {
-    let (locks, keys, height) = parse(input);
-    locks
-        .into_iter()
-        .cartesian_product(keys)
-        .filter(|(l, k)| l.iter().zip(k).all(|(a, b)| a + b <= height))
-        .count() as u64
-}
+    fn part1((locks, keys, height): &Self::Input) -> Answer {
+        Answer::from(
+            locks
+                .iter()
+                .cartesian_product(keys)
+                .filter(|(l, k)| l.iter().zip(k.iter()).all(|(a, b)| a + b <= *height))
+                .count() as u64,
+        )
+    }
+
+    fn part2(_: &Self::Input) -> Answer {
+        Answer::None
+    }
 
-pub fn part2(_: String) -> u64 {
-    0
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(3)]
+    }
 }
@@ -1,3 +1,5 @@
+use crate::solution::solutions;
+
 pub mod day01;
 pub mod day02;
 pub mod day03;
@@ -23,3 +25,117 @@ pub mod day22;
 pub mod day23;
 pub mod day24;
 pub mod day25;
+
+solutions!(
+    2024;
+    day01 => Day01,
+    day02 => Day02,
+    day03 => Day03,
+    day04 => Day04,
+    day05 => Day05,
+    day06 => Day06,
+    day07 => Day07,
+    day08 => Day08,
+    day09 => Day09,
+    day10 => Day10,
+    day11 => Day11,
+    day12 => Day12,
+    day13 => Day13,
+    day14 => Day14,
+    day15 => Day15,
+    day16 => Day16,
+    day17 => Day17,
+    day18 => Day18,
+    day19 => Day19,
+    day20 => Day20,
+    day21 => Day21,
+    day22 => Day22,
+    day23 => Day23,
+    day24 => Day24,
+    day25 => Day25,
+);
+
+#[cfg(test)]
+mod malformed {
+    use crate::{solution::get, util::Answer};
+
+    fn parse_error(day: u8, input: &str) -> String {
+        match get(2024, day).unwrap().parse(input) {
+            Ok(_) => panic!("day {day} parsed {input:?}"),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    fn solve(day: u8, example: usize, input: &str) -> [Answer; 2] {
+        let solution = get(2024, day).unwrap();
+        let parsed = solution.parse_as_example(example, input).unwrap().unwrap();
+        [1, 2].map(|part| solution.solve(part, &*parsed).unwrap())
+    }
+
+    #[test]
+    fn inputs_are_rejected_by_parse() {
+        assert_eq!(
+            parse_error(4, "XMAS\nXMA"),
+            "line 2, column 1: expected 4 columns, found 3"
+        );
+        assert_eq!(
+            parse_error(4, "1 2 3"),
+            "line 1, column 1: expected X, M, A or S, found `1`"
+        );
+        assert_eq!(parse_error(6, "..#\n..."), "line 1, column 1: no `^` in the grid");
+        assert_eq!(
+            parse_error(15, "####\n#@.#\n####\n\n<é>"),
+            "line 5, column 2: expected a move, found `é`"
+        );
+        assert_eq!(
+            parse_error(15, "####\n#@x#\n####\n\n<>"),
+            "line 2, column 3: expected a wall, box, robot or floor, found `x`"
+        );
+        assert_eq!(
+            parse_error(16, "#####\n#S.E.\n#####"),
+            "line 2, column 5: expected `#` around the grid, found `.`"
+        );
+        assert_eq!(
+            parse_error(17, "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 0,7,9,1"),
+            "line 5, column 12: combo operand 7 is reserved"
+        );
+        assert_eq!(
+            parse_error(17, "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 1,7,9,1"),
+            "line 5, column 14: expected 0 to 7, found 9"
+        );
+        assert_eq!(
+            parse_error(21, "029A\n12B"),
+            "line 2, column 3: expected a keypad button, found `B`"
+        );
+        assert_eq!(
+            parse_error(24, "x00: 1\n\nx00 AND y00 -> z00"),
+            "line 3, column 9: no value or gate for wire `y00`"
+        );
+        assert_eq!(
+            parse_error(24, "x00: 1\n\nx00 AND a -> z00\nz00 OR x00 -> a"),
+            "line 4, column 1: wire `a` depends on itself"
+        );
+        assert_eq!(parse_error(22, ""), "line 1, column 1: expected a secret number");
+        assert_eq!(parse_error(23, ""), "line 1, column 1: expected a connection");
+    }
+
+    #[test]
+    fn unsolvable_inputs_have_no_answer() {
+        assert_eq!(solve(4, 1, "XM\nAS"), [Answer::Int(0), Answer::Int(0)]);
+        let walled = "#####\n#S#E#\n#####";
+        assert_eq!(solve(16, 1, walled), [Answer::None, Answer::None]);
+        assert_eq!(solve(20, 1, walled), [Answer::None, Answer::None]);
+        let cut_off = (0..7).map(|x| format!("{x},1")).collect::<Vec<_>>().join("\n");
+        assert_eq!(solve(18, 1, &cut_off)[0], Answer::None);
+        assert_eq!(solve(18, 1, "0,1"), [Answer::Int(12), Answer::None]);
+        let apart = (0..10)
+            .map(|i| format!("p={},{} v=0,0", i % 6 * 2, i / 6 * 2))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(solve(14, 1, &apart)[1], Answer::None);
+        let program = |numbers: &str| format!("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: {numbers}");
+        assert_eq!(solve(17, 1, &program("0,1")), [Answer::from(""), Answer::None]);
+        assert_eq!(solve(17, 1, &program("3,0"))[0], Answer::None);
+        assert_eq!(solve(17, 2, &program(&["0,3,5,4,3,0"; 4].join(",")))[1], Answer::None);
+    }
+}
@@ -1,3 +1,19 @@
-pub mod aoc24;
+#![allow(clippy::upper_case_acronyms)]
+
 pub mod math;
+pub mod solution;
 pub mod util;
+
+/// Declares each year's module and collects their solutions for the registry.
+macro_rules! years {
+    ( $( $year:ident ),* $(,)? ) => {
+        $( pub mod $year; )*
+
+        /// Every year's solutions.
+        pub(crate) static YEARS: &[&[&dyn solution::DynSolution]] = &[$($year::SOLUTIONS),*];
+    };
+}
+
+years! {
+    aoc24,
+}
@@ -1,83 +1,718 @@
-mod aoc24;
-mod math;
-mod util;
+use std::{
+    any::Any,
+    collections::HashMap,
+    io::Read,
+    iter,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, Instant},
+};
 
-#[cfg(test)]
-use aoc24::*;
-#[cfg(test)]
-use util::{fetch_input, submit};
+use aoc2024::{
+    solution::{self, DynSolution, Example},
+    util::{
+        fetch_input, fetch_puzzle,
+        history::{self, History},
+        scaffold_day, session, submit, verify, Answer, Expected, FsCache, PeakAlloc, PuzzleCache,
+        Verdict, Watcher,
+    },
+};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use itertools::Itertools;
+use rayon::prelude::*;
+use serde::Serialize;
 
-#[cfg(test)]
-use paste::paste;
+#[global_allocator]
+static ALLOC: PeakAlloc = PeakAlloc::new();
 
-#[tokio::main]
-async fn main() {
-    use aoc24::*;
-    use util::fetch_input;
-    let input = fetch_input(2024, 20).await;
-    let result = day20::part1(input);
-    println!("{result}");
-}
-
-#[cfg(test)]
-macro_rules! day {
-    ( $day:ident ) => {
-        stringify!($day)[3..].parse::<u8>().unwrap()
+#[derive(Debug, Parser)]
+#[command(about = "Advent of Code solution runner")]
+struct Cli {
+    /// adventofcode.com session token, overriding AOC_SESSION and the config file
+    #[arg(long, global = true)]
+    session: Option<String>,
+    /// How `run`, `check` and `run-all` print their results
+    #[arg(long, global = true, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Human readable lines and tables
+    Text,
+    /// One JSON object per part, one per line
+    Json,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run one day and print its answers
+    Run {
+        #[command(flatten)]
+        day: DayArgs,
+        /// Keep running, and run again on the input and the puzzle's examples whenever they change
+        #[arg(short, long)]
+        watch: bool,
+    },
+    /// Run and check every day of a year, or of every year
+    RunAll {
+        #[arg(short, long)]
+        year: Option<u16>,
+        /// Run days on the rayon thread pool instead of one at a time (timings get noisier)
+        #[arg(long)]
+        parallel: bool,
+    },
+    /// Time one day over several iterations
+    Bench {
+        #[command(flatten)]
+        day: DayArgs,
+        #[arg(short = 'n', long, default_value_t = 10)]
+        iterations: u32,
+    },
+    /// Run one day and compare its answers against the known ones
+    Check(DayArgs),
+    /// Run one part of a day and submit its answer to adventofcode.com
+    Submit(DayArgs),
+    /// Create the module for a new day from a template and register it
+    New {
+        #[arg(short, long, default_value_t = solution::latest_year())]
+        year: u16,
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Download a day's puzzle text and examples and print it as Markdown
+    Puzzle {
+        #[arg(short, long, default_value_t = solution::latest_year())]
+        year: u16,
+        #[arg(short, long)]
+        day: u8,
+    },
+    /// Compare recorded `cargo bench` timings between two commits
+    BenchReport {
+        /// Commit to compare against (the one recorded before --commit by default)
+        #[arg(long)]
+        baseline: Option<String>,
+        /// Commit to check (the latest recorded by default)
+        #[arg(long)]
+        commit: Option<String>,
+        /// Percentage slowdown reported as a regression
+        #[arg(short, long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+}
+
+#[derive(Debug, Args)]
+struct DayArgs {
+    #[arg(short, long, default_value_t = solution::latest_year())]
+    year: u16,
+    #[arg(short, long)]
+    day: u8,
+    /// Only run this part (both parts are run by default)
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Read the puzzle input from this file, or `-` for stdin, instead of the input cache
+    #[arg(short, long)]
+    input: Option<String>,
+    /// Solve with the puzzle parameters of the solution's `n`th example and check the answers
+    /// against it, using the example's own input unless --input is given
+    #[arg(short, long)]
+    example: Option<usize>,
+}
+
+impl DayArgs {
+    /// Where the input is read from, for error messages.
+    fn input_name(&self) -> String {
+        match (self.input.as_deref(), self.example) {
+            (Some("-"), _) => "stdin".to_string(),
+            (Some(path), _) => path.to_string(),
+            (None, Some(n)) => format!("example {n}"),
+            (None, None) => FsCache::from_env()
+                .path(self.year, self.day)
+                .display()
+                .to_string(),
+        }
+    }
+
+    /// The part picked with --part, or else the parts the picked example has answers for, or
+    /// else both.
+    fn parts(&self) -> Vec<u8> {
+        match (self.part, self.example().ok().flatten()) {
+            (Some(part), _) => vec![part],
+            (None, Some(example)) => [1, 2]
+                .into_iter()
+                .filter(|&part| example.expected(part).is_some())
+                .collect(),
+            (None, None) => vec![1, 2],
+        }
+    }
+
+    /// The example picked with --example, if any.
+    fn example(&self) -> Result<Option<Example>, String> {
+        let Some(n) = self.example else {
+            return Ok(None);
+        };
+        find_solution(self.year, self.day)?
+            .example(n)
+            .map(Some)
+            .ok_or_else(|| format!("{} day {} has no example {n}", self.year, self.day))
+    }
+
+    async fn load_input(&self) -> Result<String, String> {
+        let input = match (self.input.as_deref(), self.example()?) {
+            (None, Some(example)) => example.input.to_string(),
+            (Some("-"), _) => {
+                let mut buf = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut buf)
+                    .map_err(|e| format!("couldn't read stdin: {e}"))?;
+                buf
+            }
+            (Some(path), _) => {
+                std::fs::read_to_string(path).map_err(|e| format!("couldn't read {path}: {e}"))?
+            }
+            (None, None) => fetch_input(self.year, self.day)
+                .await
+                .map_err(|e| format!("couldn't fetch input: {e}"))?,
+        };
+        Ok(input.trim().to_string())
+    }
+}
+
+/// Parses `input`, with the config of the `example`th example if there is one, naming `name` as
+/// its source if it can't be parsed.
+fn parse_input(
+    solution: &dyn DynSolution,
+    name: &str,
+    input: &str,
+    example: Option<usize>,
+) -> Result<Box<dyn Any>, String> {
+    let parsed = match example {
+        None => solution.parse(input),
+        Some(n) => solution.parse_as_example(n, input).ok_or_else(|| {
+            format!(
+                "{} day {} has no example {n}",
+                solution.year(),
+                solution.day()
+            )
+        })?,
     };
+    parsed.map_err(|e| format!("couldn't parse {name}: {e}"))
+}
+
+/// Checks `answer` against the real input's known answer, or the `example`th example's.
+fn check_answer(
+    solution: &dyn DynSolution,
+    part: u8,
+    answer: &Answer,
+    example: Option<usize>,
+) -> Verdict {
+    match example {
+        None => verify(solution.year(), solution.day(), part, answer),
+        Some(n) => {
+            let expected = solution
+                .example(n)
+                .and_then(|example| example.expected(part).cloned());
+            Verdict::check(expected.map_or(Expected::Unknown, Expected::Answer), answer)
+        }
+    }
+}
+
+fn find_solution(year: u16, day: u8) -> Result<&'static dyn DynSolution, String> {
+    solution::get(year, day).ok_or_else(|| format!("no solution for {year} day {day}"))
+}
+
+/// Runs `f` and returns its result along with how long it took.
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let res = f();
+    (res, start.elapsed())
+}
+
+fn run(args: &DayArgs, input: String, check: bool, format: Format) -> Result<bool, String> {
+    let solution = find_solution(args.year, args.day)?;
+    let reports = run_day(
+        solution,
+        &args.input_name(),
+        &input,
+        &args.parts(),
+        args.example,
+        true,
+    )?;
+    if format == Format::Json {
+        print_json(&reports);
+        return Ok(reports.iter().all(|r| r.verdict.is_ok()));
+    }
+    if let Some(r) = reports.first() {
+        println!("day {} parsed ({:.2?})", args.day, r.parse);
+    }
+    let mut all_right = true;
+    for r in reports {
+        print!(
+            "day {} part {}: {} ({:.2?})",
+            r.day, r.part, r.answer, r.solve
+        );
+        if check {
+            all_right &= r.verdict.is_ok();
+            print!(" {}", r.verdict);
+        }
+        println!();
+    }
+    Ok(all_right)
+}
+
+/// Prints one JSON record per line.
+fn print_json(reports: &[PartReport]) {
+    for r in reports {
+        println!("{}", serde_json::to_string(r).unwrap());
+    }
+}
+
+/// The files `run --watch` runs on: the input, then every example extracted from the puzzle.
+fn watched_files(args: &DayArgs) -> Vec<PathBuf> {
+    let input = args.input.as_ref().map_or_else(
+        || FsCache::from_env().path(args.year, args.day),
+        PathBuf::from,
+    );
+    let puzzles = PuzzleCache::from_env();
+    let examples = (1..)
+        .map(|n| puzzles.example_path(args.year, args.day, n))
+        .take_while(|path| path.exists());
+    iter::once(input).chain(examples).collect()
+}
+
+/// Solves `part`, catching a panic so that one bad input doesn't stop `run --watch`.
+fn solve_caught(solution: &dyn DynSolution, part: u8, parsed: &dyn Any) -> Option<Answer> {
+    panic::catch_unwind(AssertUnwindSafe(|| solution.solve(part, parsed)))
+        .ok()
+        .flatten()
+}
+
+/// What to run on one watched file: each selected part, with the example whose config it's
+/// parsed with and whose answer it's checked against, if any.
+fn watch_plan(
+    solution: &dyn DynSolution,
+    is_input: bool,
+    input: &str,
+    parts: &[u8],
+) -> Vec<(u8, Option<usize>)> {
+    if is_input {
+        return parts.iter().map(|&part| (part, None)).collect();
+    }
+    // Several examples can share an input, like ones with different configs for each part.
+    let matching = (1..)
+        .map_while(|n| solution.example(n).map(|example| (n, example)))
+        .filter(|(_, example)| example.input.trim() == input)
+        .collect_vec();
+    parts
+        .iter()
+        .filter_map(|&part| {
+            let (n, _) = matching
+                .iter()
+                .find(|(_, example)| example.expected(part).is_some())?;
+            Some((part, Some(*n)))
+        })
+        .collect()
 }
 
-#[cfg(test)]
-macro_rules! run_day_test {
-    ( $day:ident ) => {
-        paste! {
-            #[test]
-            fn [<test_ $day>]() {
-                let runtime = tokio::runtime::Runtime::new().unwrap();
-                let day = day!($day);
-                let input = runtime.block_on(fetch_input(2024, day)).trim().to_string();
-                {
-                    let res = $day::part1(input.clone());
-                    let output = submit(2024, day, 1, format!("{res}"));
-                    println!("{day}::part1 {}", res);
-                    assert!(output.starts_with("right"));
+/// Runs the selected parts on every watched file whenever one of them changes, showing how each
+/// answer changed since the previous run. Examples run with their own config, and only for the
+/// parts the solution has example answers for.
+async fn watch(args: &DayArgs) -> Result<(), String> {
+    if args.input.as_deref() == Some("-") {
+        return Err("can't watch stdin".to_string());
+    }
+    if args.example.is_some() {
+        return Err("--watch already runs every example".to_string());
+    }
+    let solution = find_solution(args.year, args.day)?;
+    if args.input.is_none() {
+        // Download the input up front so there is something to watch.
+        if let Err(e) = fetch_input(args.year, args.day).await {
+            eprintln!("couldn't fetch input: {e}");
+        }
+    }
+
+    let mut watcher = Watcher::new();
+    let mut previous = HashMap::new();
+    loop {
+        let files = watched_files(args);
+        if watcher.poll(&files) {
+            println!("--- {} day {} ---", args.year, args.day);
+            for (i, file) in files.iter().enumerate() {
+                let Ok(input) = std::fs::read_to_string(file) else {
+                    continue;
+                };
+                let input = input.trim();
+                let name = file.display().to_string();
+                let plan = watch_plan(solution, i == 0, input, &args.parts());
+                if plan.is_empty() {
+                    println!("{name}: no example answers to check");
                 }
-                {
-                    let res = $day::part2(input);
-                    let output = submit(2024, day, 2, format!("{res}"));
-                    println!("{day}::part2 {}", res);
-                    assert!(output.starts_with("right"));
+                for (example, parts) in &plan.into_iter().chunk_by(|&(_, example)| example) {
+                    let parsed = match parse_input(solution, &name, input, example) {
+                        Ok(parsed) => parsed,
+                        Err(e) => {
+                            println!("{e}");
+                            continue;
+                        }
+                    };
+                    for (part, _) in parts {
+                        let (answer, elapsed) = timed(|| solve_caught(solution, part, &*parsed));
+                        let Some(answer) = answer else {
+                            println!("{name} part {part}: panicked");
+                            previous.remove(&(file.clone(), part));
+                            continue;
+                        };
+                        let verdict = match example {
+                            Some(_) => {
+                                format!(" {}", check_answer(solution, part, &answer, example))
+                            }
+                            None => String::new(),
+                        };
+                        let answer = answer.to_string();
+                        let change = match previous.insert((file.clone(), part), answer.clone()) {
+                            None => String::new(),
+                            Some(old) if old == answer => " (unchanged)".to_string(),
+                            Some(old) => format!(" (was {old})"),
+                        };
+                        println!("{name} part {part}: {answer} ({elapsed:.2?}){verdict}{change}");
+                    }
                 }
             }
         }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+}
+
+fn report_times(label: &str, times: Vec<Duration>) {
+    let times = times.into_iter().sorted().collect_vec();
+    let mean = times.iter().sum::<Duration>() / times.len() as u32;
+    println!(
+        "{label}: min {:.2?}, median {:.2?}, mean {mean:.2?}, max {:.2?}",
+        times[0],
+        times[times.len() / 2],
+        times[times.len() - 1],
+    );
+}
+
+fn bench(args: &DayArgs, input: String, iterations: u32) -> Result<(), String> {
+    let solution = find_solution(args.year, args.day)?;
+    let iterations = iterations.max(1);
+    let name = args.input_name();
+    let parsed = parse_input(solution, &name, &input, args.example)?;
+    let parse_times = (0..iterations)
+        .map(|_| timed(|| parse_input(solution, &name, &input, args.example)).1)
+        .collect_vec();
+    report_times(&format!("day {} parse", args.day), parse_times);
+    for part in args.parts() {
+        let times = (0..iterations)
+            .map(|_| timed(|| solution.solve(part, &*parsed)).1)
+            .collect_vec();
+        report_times(&format!("day {} part {part}", args.day), times);
+    }
+    Ok(())
+}
+
+async fn submit_answer(args: &DayArgs, input: String) -> Result<(), String> {
+    let part = args
+        .part
+        .ok_or_else(|| "pick a part to submit with --part".to_owned())?;
+    if args.example.is_some() {
+        return Err("only answers to the real input can be submitted".to_owned());
+    }
+    let solution = find_solution(args.year, args.day)?;
+    let parsed = parse_input(solution, &args.input_name(), &input, None)?;
+    let answer = solution.solve(part, &*parsed).unwrap();
+    if answer == Answer::None {
+        return Err(format!("day {} has no part {part}", args.day));
+    }
+    println!("day {} part {part}: submitting {answer}", args.day);
+    let outcome = submit(args.year, args.day, part, answer)
+        .await
+        .map_err(|e| format!("couldn't submit: {e}"))?;
+    println!("{outcome}");
+    Ok(())
+}
+
+fn bench_report(
+    baseline: Option<String>,
+    commit: Option<String>,
+    threshold: f64,
+) -> Result<(), String> {
+    let history = History::from_env()
+        .load()
+        .map_err(|e| format!("couldn't read the bench history: {e}"))?;
+    let commits = history::commits(&history);
+    let after = match commit.as_deref() {
+        Some(commit) => commits
+            .iter()
+            .position(|&c| c == commit)
+            .ok_or_else(|| format!("no timings recorded for {commit}"))?,
+        None => commits
+            .len()
+            .checked_sub(1)
+            .ok_or_else(|| "the bench history is empty, run `cargo bench` first".to_owned())?,
+    };
+    let before = match baseline.as_deref() {
+        Some(baseline) => commits
+            .iter()
+            .find(|&&c| c == baseline)
+            .ok_or_else(|| format!("no timings recorded for {baseline}"))?,
+        None => after
+            .checked_sub(1)
+            .map(|i| commits[i])
+            .ok_or_else(|| format!("nothing recorded before {} to compare with", commits[after]))?,
+    };
+    let after = commits[after];
+
+    println!("{before} -> {after}");
+    let mut regressions = 0;
+    for change in history::compare(&history, before, after) {
+        let flag = if change.is_regression(threshold) {
+            regressions += 1;
+            "  REGRESSION"
+        } else {
+            ""
+        };
+        println!(
+            "{} day {:>2} {}: {:.2?} -> {:.2?} ({:+.1}%){flag}",
+            change.year,
+            change.day,
+            change.phase,
+            change.before,
+            change.after,
+            (change.ratio() - 1.0) * 100.0,
+        );
+    }
+    match regressions {
+        0 => Ok(()),
+        n => Err(format!("{n} timings regressed by more than {threshold}%")),
+    }
+}
+
+fn serialize_nanos<S: serde::Serializer>(d: &Duration, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_u64(d.as_nanos() as u64)
+}
+
+/// The result of running one part of a day: a row of the `run-all` table, or a JSON record.
+#[derive(Debug, Serialize)]
+struct PartReport {
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: String,
+    expected: Option<String>,
+    status: &'static str,
+    #[serde(rename = "parse_ns", serialize_with = "serialize_nanos")]
+    parse: Duration,
+    #[serde(rename = "solve_ns", serialize_with = "serialize_nanos")]
+    solve: Duration,
+    /// Most heap bytes allocated at once while parsing and solving, on top of what was already
+    /// allocated. Only measured when days run one at a time.
+    peak_bytes: Option<usize>,
+    #[serde(skip)]
+    verdict: Verdict,
+}
+
+impl PartReport {
+    /// The time to report for this part in a table, where the parse counts towards part 1.
+    fn elapsed(&self) -> Duration {
+        self.solve
+            + if self.part == 1 {
+                self.parse
+            } else {
+                Duration::ZERO
+            }
+    }
+}
+
+/// Parses `input`, read from `name`, once and solves `parts` of `solution`, checking each answer
+/// against the real input's or `example`'s. The peak allocation is only meaningful when nothing
+/// else is running, so it's left out unless `measure` is set.
+fn run_day(
+    solution: &dyn DynSolution,
+    name: &str,
+    input: &str,
+    parts: &[u8],
+    example: Option<usize>,
+    measure: bool,
+) -> Result<Vec<PartReport>, String> {
+    let base = ALLOC.current();
+    ALLOC.reset_peak();
+    let (parsed, parse) = timed(|| parse_input(solution, name, input, example));
+    let parsed = parsed?;
+    let parse_peak = ALLOC.peak().saturating_sub(base);
+    Ok(parts
+        .iter()
+        .map(|&part| {
+            ALLOC.reset_peak();
+            let (answer, solve) = timed(|| solution.solve(part, &*parsed).unwrap());
+            let peak = ALLOC.peak().saturating_sub(base).max(parse_peak);
+            let verdict = check_answer(solution, part, &answer, example);
+            PartReport {
+                year: solution.year(),
+                day: solution.day(),
+                part,
+                answer: answer.to_string(),
+                expected: match &verdict {
+                    Verdict::Right => Some(answer.to_string()),
+                    Verdict::Wrong { expected } => Some(expected.to_string()),
+                    Verdict::Unsolved | Verdict::NoPuzzle => None,
+                },
+                status: verdict.status(),
+                parse,
+                solve,
+                peak_bytes: measure.then_some(peak),
+                verdict,
+            }
+        })
+        .collect())
+}
+
+async fn new_day(year: u16, day: u8) -> Result<(), String> {
+    let example = match fetch_puzzle(year, day).await {
+        Ok(puzzle) => puzzle.examples.into_iter().next(),
+        Err(e) => {
+            eprintln!("couldn't fetch the puzzle, leaving the example empty: {e}");
+            None
+        }
     };
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let path = scaffold_day(root, year, day, example.as_deref())
+        .map_err(|e| format!("couldn't create {year} day {day}: {e}"))?;
+    println!("created {}", path.display());
+    Ok(())
+}
+
+async fn run_all(year: Option<u16>, parallel: bool, format: Format) -> Result<(), String> {
+    let mut days = Vec::new();
+    for solution in solution::all().filter(|s| year.is_none_or(|year| s.year() == year)) {
+        let (year, day) = (solution.year(), solution.day());
+        match fetch_input(year, day).await {
+            Ok(input) => days.push((
+                solution,
+                FsCache::from_env().path(year, day).display().to_string(),
+                input.trim().to_string(),
+            )),
+            Err(e) => eprintln!("skipping {year} day {day}: {e}"),
+        }
+    }
+
+    let (reports, wall_time) = timed(|| {
+        if parallel {
+            days.par_iter()
+                .map(|(solution, name, input)| {
+                    run_day(*solution, name, input, &[1, 2], None, false)
+                })
+                .collect::<Vec<_>>()
+        } else {
+            days.iter()
+                .map(|(solution, name, input)| run_day(*solution, name, input, &[1, 2], None, true))
+                .collect()
+        }
+    });
+    let (reports, failed): (Vec<_>, Vec<_>) = reports.into_iter().partition_result();
+    for e in &failed {
+        eprintln!("{e}");
+    }
+    let reports = reports.into_iter().flatten().collect_vec();
+    let wrong = reports
+        .iter()
+        .filter(|r| matches!(r.verdict, Verdict::Wrong { .. }))
+        .count();
+    match format {
+        Format::Text => print_table(&reports, wall_time),
+        Format::Json => print_json(&reports),
+    }
+    match (wrong, failed.len()) {
+        (0, 0) => Ok(()),
+        (0, n) => Err(format!("{n} inputs couldn't be parsed")),
+        (n, _) => Err(format!("{n} answers were wrong")),
+    }
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    run_day_test!(day01);
-    run_day_test!(day02);
-    run_day_test!(day03);
-    run_day_test!(day04);
-    run_day_test!(day05);
-    run_day_test!(day06);
-    run_day_test!(day07);
-    run_day_test!(day08);
-    run_day_test!(day09);
-    run_day_test!(day10);
-    run_day_test!(day11);
-    run_day_test!(day12);
-    run_day_test!(day13);
-    run_day_test!(day14);
-    run_day_test!(day15);
-    run_day_test!(day16);
-    run_day_test!(day17);
-    run_day_test!(day18);
-    run_day_test!(day19);
-    run_day_test!(day20);
-    run_day_test!(day21);
-    run_day_test!(day22);
-    run_day_test!(day23);
-    run_day_test!(day24);
-    run_day_test!(day25);
+fn print_table(reports: &[PartReport], wall_time: Duration) {
+    let answer_width = reports
+        .iter()
+        .map(|r| r.answer.len())
+        .max()
+        .unwrap_or(0)
+        .max(6);
+    let status_width = reports
+        .iter()
+        .map(|r| r.verdict.to_string().len())
+        .max()
+        .unwrap_or(0)
+        .max(6);
+    println!(
+        "{:>4} {:>3} {:>4}  {:<answer_width$}  {:<status_width$}  {:>10}",
+        "year", "day", "part", "answer", "status", "time"
+    );
+    for r in reports {
+        println!(
+            "{:>4} {:>3} {:>4}  {:<answer_width$}  {:<status_width$}  {:>10}",
+            r.year,
+            r.day,
+            r.part,
+            r.answer,
+            r.verdict.to_string(),
+            format!("{:.2?}", r.elapsed()),
+        );
+    }
+    let total = reports.iter().map(PartReport::elapsed).sum::<Duration>();
+    println!("total: {total:.2?} (wall clock {wall_time:.2?})");
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    if let Some(token) = cli.session {
+        session::set_session(token);
+    }
+    let result = match cli.command {
+        Command::Run { day, watch: true } => watch(&day).await,
+        Command::Run { day, watch: false } => day
+            .load_input()
+            .await
+            .and_then(|input| run(&day, input, false, cli.format))
+            .map(|_| ()),
+        Command::Check(args) => args
+            .load_input()
+            .await
+            .and_then(|input| run(&args, input, true, cli.format))
+            .and_then(|right| {
+                right
+                    .then_some(())
+                    .ok_or_else(|| "some answers were wrong".to_owned())
+            }),
+        Command::Bench { day, iterations } => day
+            .load_input()
+            .await
+            .and_then(|input| bench(&day, input, iterations)),
+        Command::Submit(args) => match args.load_input().await {
+            Ok(input) => submit_answer(&args, input).await,
+            Err(e) => Err(e),
+        },
+        Command::BenchReport {
+            baseline,
+            commit,
+            threshold,
+        } => bench_report(baseline, commit, threshold),
+        Command::New { year, day } => new_day(year, day).await,
+        Command::Puzzle { year, day } => fetch_puzzle(year, day)
+            .await
+            .map(|puzzle| print!("{}", puzzle.markdown))
+            .map_err(|e| format!("couldn't fetch the puzzle: {e}")),
+        Command::RunAll { year, parallel } => run_all(year, parallel, cli.format).await,
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -0,0 +1,283 @@
+use std::{any::Any, collections::BTreeMap, marker::PhantomData, sync::LazyLock};
+
+use itertools::Itertools;
+
+use crate::util::{Answer, ParseError};
+
+/// A single day's puzzle: how to parse its input and how to solve each part.
+pub trait Solution {
+    const YEAR: u16;
+    const DAY: u8;
+
+    /// Puzzle parameters, like grid sizes, that differ between the examples and the real input.
+    /// The default is the real puzzle's.
+    type Config: Default;
+    type Input: 'static;
+
+    fn parse(input: &str, config: &Self::Config) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Answer;
+    fn part2(input: &Self::Input) -> Answer;
+
+    /// Worked examples from the puzzle text, checked by the offline test suite.
+    fn examples() -> Vec<Example<Self::Config>> {
+        Vec::new()
+    }
+}
+
+/// An example input with the answers the puzzle text gives for it.
+#[derive(Debug, Clone)]
+pub struct Example<C = ()> {
+    pub input: &'static str,
+    pub config: C,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl<C: Default> Example<C> {
+    pub fn new(input: &'static str) -> Self {
+        Self {
+            input,
+            config: C::default(),
+            part1: None,
+            part2: None,
+        }
+    }
+}
+
+impl<C> Example<C> {
+    pub fn config(mut self, config: C) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn part1(mut self, answer: impl Into<Answer>) -> Self {
+        self.part1 = Some(answer.into());
+        self
+    }
+
+    pub fn part2(mut self, answer: impl Into<Answer>) -> Self {
+        self.part2 = Some(answer.into());
+        self
+    }
+
+    pub fn expected(&self, part: u8) -> Option<&Answer> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// The example without its config, for code that doesn't know the config's type.
+    pub fn erase_config(self) -> Example {
+        Example {
+            input: self.input,
+            config: (),
+            part1: self.part1,
+            part2: self.part2,
+        }
+    }
+}
+
+/// Object-safe view of a [`Solution`], so days with different input types can share a registry.
+pub trait DynSolution: Sync {
+    fn year(&self) -> u16;
+    fn day(&self) -> u8;
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError>;
+    /// The `n`th of the day's examples, counting from 1.
+    fn example(&self, n: usize) -> Option<Example>;
+    /// Parses `input` with the config of the `n`th example instead of the real puzzle's, or
+    /// `None` if there is no such example.
+    fn parse_as_example(&self, n: usize, input: &str) -> Option<Result<Box<dyn Any>, ParseError>>;
+    fn part1(&self, input: &dyn Any) -> Answer;
+    fn part2(&self, input: &dyn Any) -> Answer;
+
+    /// Solves `part` on input previously returned by [`DynSolution::parse`].
+    fn solve(&self, part: u8, input: &dyn Any) -> Option<Answer> {
+        match part {
+            1 => Some(self.part1(input)),
+            2 => Some(self.part2(input)),
+            _ => None,
+        }
+    }
+}
+
+pub struct Registered<S>(PhantomData<fn() -> S>);
+
+impl<S> Registered<S> {
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<S> Default for Registered<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Solution> Registered<S> {
+    fn input<'a>(&self, input: &'a dyn Any) -> &'a S::Input {
+        input
+            .downcast_ref()
+            .unwrap_or_else(|| panic!("input was not parsed by {} day {}", S::YEAR, S::DAY))
+    }
+
+    fn nth_example(n: usize) -> Option<Example<S::Config>> {
+        S::examples().into_iter().nth(n.checked_sub(1)?)
+    }
+}
+
+impl<S: Solution> DynSolution for Registered<S> {
+    fn year(&self) -> u16 {
+        S::YEAR
+    }
+
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(S::parse(input, &S::Config::default())?))
+    }
+
+    fn example(&self, n: usize) -> Option<Example> {
+        Some(Self::nth_example(n)?.erase_config())
+    }
+
+    fn parse_as_example(&self, n: usize, input: &str) -> Option<Result<Box<dyn Any>, ParseError>> {
+        let example = Self::nth_example(n)?;
+        Some(S::parse(input, &example.config).map(|parsed| Box::new(parsed) as Box<dyn Any>))
+    }
+
+    fn part1(&self, input: &dyn Any) -> Answer {
+        S::part1(self.input(input))
+    }
+
+    fn part2(&self, input: &dyn Any) -> Answer {
+        S::part2(self.input(input))
+    }
+}
+
+/// Declares a year's `SOLUTIONS` from its day modules, along with tests running each day on
+/// its examples and on the real input.
+macro_rules! solutions {
+    ( $year:literal; $( $day:ident => $solution:ident ),* $(,)? ) => {
+        #[doc = concat!("Every ", stringify!($year), " solution, in day order.")]
+        pub static SOLUTIONS: &[&dyn $crate::solution::DynSolution] =
+            &[$(&$crate::solution::Registered::<$day::$solution>::new()),*];
+
+        #[cfg(test)]
+        mod test {
+            $(
+                #[test]
+                fn $day() {
+                    $crate::solution::test_solution($year, stringify!($day)[3..].parse().unwrap());
+                }
+            )*
+
+            /// Puzzle examples only, so these run without any personal input.
+            mod examples {
+                $(
+                    #[test]
+                    fn $day() {
+                        $crate::solution::test_examples::<super::super::$day::$solution>();
+                    }
+                )*
+            }
+        }
+    };
+}
+
+pub(crate) use solutions;
+
+static REGISTRY: LazyLock<BTreeMap<(u16, u8), &'static dyn DynSolution>> = LazyLock::new(|| {
+    crate::YEARS
+        .iter()
+        .flat_map(|solutions| solutions.iter())
+        .map(|&s| ((s.year(), s.day()), s))
+        .collect()
+});
+
+/// Looks up the solution for `day` of `year`.
+pub fn get(year: u16, day: u8) -> Option<&'static dyn DynSolution> {
+    REGISTRY.get(&(year, day)).copied()
+}
+
+/// Every registered solution, ordered by year and day.
+pub fn all() -> impl Iterator<Item = &'static dyn DynSolution> {
+    REGISTRY.values().copied()
+}
+
+/// Every year with at least one solution, in order.
+pub fn years() -> impl Iterator<Item = u16> {
+    REGISTRY.keys().map(|&(year, _)| year).dedup()
+}
+
+/// The most recent year with any solutions.
+pub fn latest_year() -> u16 {
+    years().last().expect("no solutions are registered")
+}
+
+#[cfg(test)]
+pub(crate) fn test_solution(year: u16, day: u8) {
+    use crate::util::{fetch_input, verify, InputError};
+
+    let solution = get(year, day).unwrap();
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let input = match runtime.block_on(fetch_input(year, day)) {
+        Ok(input) => input,
+        Err(e @ (InputError::Offline { .. } | InputError::MissingCookie)) => {
+            eprintln!("skipping {year} day {day}: {e}");
+            return;
+        }
+        Err(e) => panic!("couldn't fetch input for {year} day {day}: {e}"),
+    };
+    let input = input.trim();
+    let parsed = solution
+        .parse(input)
+        .unwrap_or_else(|e| panic!("couldn't parse {year} day {day}: {e}"));
+    for part in [1, 2] {
+        let res = solution.solve(part, &*parsed).unwrap();
+        let verdict = verify(year, day, part, &res);
+        println!("{day}::part{part} {res}");
+        assert!(verdict.is_ok(), "{day}::part{part} was {verdict}");
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn test_examples<S: Solution>() {
+    for (n, example) in (1..).zip(S::examples()) {
+        let parsed = S::parse(example.input.trim(), &example.config)
+            .unwrap_or_else(|e| panic!("{} day {} example {n}: {e}", S::YEAR, S::DAY));
+        for (part, solve) in [(1, S::part1 as fn(&S::Input) -> Answer), (2, S::part2)] {
+            if let Some(expected) = example.expected(part) {
+                let res = solve(&parsed);
+                assert_eq!(
+                    &res,
+                    expected,
+                    "{} day {} example {n} part {part}",
+                    S::YEAR,
+                    S::DAY
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn examples_parse_with_their_config() {
+        let day14 = get(2024, 14).unwrap();
+        let example = day14.example(1).unwrap();
+        let parsed = day14.parse_as_example(1, example.input).unwrap().unwrap();
+        assert_eq!(day14.solve(1, &*parsed).as_ref(), example.expected(1));
+        let parsed = day14.parse(example.input).unwrap();
+        assert_ne!(day14.solve(1, &*parsed).as_ref(), example.expected(1));
+        assert!(day14.example(0).is_none());
+        assert!(day14.parse_as_example(2, example.input).is_none());
+    }
+}
@@ -0,0 +1,99 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The system allocator, keeping count of how many bytes are live and the most that have been
+/// live at once. Install it with `#[global_allocator]` to measure a solution's peak memory use.
+#[derive(Debug, Default)]
+pub struct PeakAlloc {
+    current: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl PeakAlloc {
+    pub const fn new() -> Self {
+        Self {
+            current: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+        }
+    }
+
+    /// Bytes currently allocated.
+    pub fn current(&self) -> usize {
+        self.current.load(Ordering::Relaxed)
+    }
+
+    /// The most bytes allocated at once since the last [`reset_peak`](Self::reset_peak).
+    pub fn peak(&self) -> usize {
+        self.peak.load(Ordering::Relaxed)
+    }
+
+    /// Starts measuring the peak again from what is allocated now.
+    pub fn reset_peak(&self) {
+        self.peak.store(self.current(), Ordering::Relaxed);
+    }
+
+    fn grow(&self, bytes: usize) {
+        let current = self.current.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        self.peak.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn shrink(&self, bytes: usize) {
+        self.current.fetch_sub(bytes, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            self.grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            self.grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        self.shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new.is_null() {
+            self.grow(new_size);
+            self.shrink(layout.size());
+        }
+        new
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tracks_peak() {
+        let alloc = PeakAlloc::new();
+        let small = Layout::array::<u64>(4).unwrap();
+        let big = Layout::array::<u64>(16).unwrap();
+        unsafe {
+            let a = alloc.alloc(small);
+            assert_eq!((alloc.current(), alloc.peak()), (32, 32));
+            let a = alloc.realloc(a, small, big.size());
+            assert_eq!((alloc.current(), alloc.peak()), (128, 160));
+            alloc.reset_peak();
+            assert_eq!(alloc.peak(), 128);
+            alloc.dealloc(a, big);
+        }
+        assert_eq!((alloc.current(), alloc.peak()), (0, 128));
+    }
+}
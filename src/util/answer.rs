@@ -0,0 +1,163 @@
+use std::fmt::Display;
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use super::coordinate::Coordinate;
+
+/// A puzzle answer as produced by a solution or stored as a known answer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Int(u64),
+    Signed(i64),
+    Text(String),
+    /// Grid positions, written `X,Y` (column then row) the way puzzles print them.
+    Coords(Vec<Coordinate>),
+    /// What a solution gives for a part that has no answer, like day 25's part 2 or a maze with
+    /// no way through.
+    None,
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Int(n) => write!(f, "{n}"),
+            Self::Signed(n) => write!(f, "{n}"),
+            Self::Text(s) => write!(f, "{s}"),
+            Self::Coords(coords) => write!(
+                f,
+                "{}",
+                coords
+                    .iter()
+                    .map(|c| format!("{},{}", c.col(), c.row()))
+                    .join(",")
+            ),
+            Self::None => write!(f, "n/a"),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    /// Answers of the same kind compare by value and integers compare numerically across
+    /// signedness. Anything else falls back to comparing what would be typed into the site.
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Int(a), Self::Int(b)) => a == b,
+            (Self::Signed(a), Self::Signed(b)) => a == b,
+            (Self::Int(a), Self::Signed(b)) | (Self::Signed(b), Self::Int(a)) => {
+                i64::try_from(*a).is_ok_and(|a| a == *b)
+            }
+            (Self::Text(a), Self::Text(b)) => a == b,
+            (Self::Coords(a), Self::Coords(b)) => a == b,
+            (Self::None, Self::None) => true,
+            (Self::None, _) | (_, Self::None) => false,
+            _ => self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl Answer {
+    /// The answer's value if it is an integer, so it can be compared against guess bounds.
+    pub fn as_number(&self) -> Option<i128> {
+        match self {
+            Self::Int(n) => Some(*n as i128),
+            Self::Signed(n) => Some(*n as i128),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_from_unsigned {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Self::Int(value as u64)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_from_signed {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Self::Signed(value as i64)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_unsigned!(u8, u16, u32, u64, usize);
+impl_from_signed!(i8, i16, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_owned())
+    }
+}
+
+impl From<Coordinate> for Answer {
+    fn from(value: Coordinate) -> Self {
+        Self::Coords(vec![value])
+    }
+}
+
+impl From<Vec<Coordinate>> for Answer {
+    fn from(value: Vec<Coordinate>) -> Self {
+        Self::Coords(value)
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::None, Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(Answer::from(1590491u64).to_string(), "1590491");
+        assert_eq!(Answer::from(-3).to_string(), "-3");
+        assert_eq!(Answer::from("de,id,ke").to_string(), "de,id,ke");
+        assert_eq!(Answer::None.to_string(), "n/a");
+        assert_eq!(
+            Answer::from(Coordinate::from_row_col(20, 15)).to_string(),
+            "15,20"
+        );
+        assert_eq!(
+            Answer::from(Coordinate::from_pathfinding((15, 20))).to_string(),
+            "15,20"
+        );
+    }
+
+    #[test]
+    fn equality() {
+        assert_eq!(Answer::Int(5), Answer::Signed(5));
+        assert_ne!(Answer::Int(5), Answer::Signed(-5));
+        assert_eq!(
+            Answer::from(Coordinate::from_row_col(20, 15)),
+            Answer::from("15,20")
+        );
+        assert_ne!(Answer::Int(5), Answer::from("6"));
+        assert_ne!(Answer::None, Answer::from("n/a"));
+        assert_eq!(Answer::from(Some(5u8)), Answer::Int(5));
+        assert_eq!(Answer::from(None::<u64>), Answer::None);
+    }
+}
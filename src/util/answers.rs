@@ -0,0 +1,229 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    fmt::Display,
+    fs::read_to_string,
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{answer::Answer, input::write_atomic};
+
+/// Directory holding one `{year}.toml` of known answers per year, `answers` by default.
+pub const ANSWERS_DIR_ENV: &str = "AOC_ANSWERS_DIR";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct DayAnswers {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    part1: Option<Answer>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    part2: Option<Answer>,
+    /// Set for days that only have one puzzle, like day 25.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    no_part2: bool,
+}
+
+/// What is known about the answer to one part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expected {
+    Answer(Answer),
+    Unknown,
+    /// The day has no such part.
+    NoPuzzle,
+}
+
+/// The known answers for one year, as stored in `{year}.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct YearAnswers(BTreeMap<String, DayAnswers>);
+
+impl YearAnswers {
+    fn key(day: u8) -> String {
+        format!("day{day:02}")
+    }
+
+    pub fn expected(&self, day: u8, part: u8) -> Expected {
+        let Some(answers) = self.0.get(&Self::key(day)) else {
+            return Expected::Unknown;
+        };
+        let answer = match part {
+            1 => &answers.part1,
+            2 if answers.no_part2 => return Expected::NoPuzzle,
+            2 => &answers.part2,
+            _ => return Expected::NoPuzzle,
+        };
+        answer.clone().map_or(Expected::Unknown, Expected::Answer)
+    }
+
+    /// Records `answer` as the right one, stored the way it would be typed into the site.
+    pub fn set(&mut self, day: u8, part: u8, answer: Answer) {
+        let answer = match answer {
+            Answer::Coords(_) => Answer::Text(answer.to_string()),
+            Answer::None => return,
+            answer => answer,
+        };
+        let answers = self.0.entry(Self::key(day)).or_default();
+        match part {
+            1 => answers.part1 = Some(answer),
+            2 => answers.part2 = Some(answer),
+            _ => {}
+        }
+    }
+}
+
+/// Known answers on disk, one file per year.
+#[derive(Debug, Clone)]
+pub struct AnswerStore {
+    dir: PathBuf,
+}
+
+impl AnswerStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The store at [`ANSWERS_DIR_ENV`], or `answers`.
+    pub fn from_env() -> Self {
+        Self::new(env::var(ANSWERS_DIR_ENV).unwrap_or_else(|_| "answers".to_string()))
+    }
+
+    pub fn path(&self, year: u16) -> PathBuf {
+        self.dir.join(format!("{year}.toml"))
+    }
+
+    /// The answers for `year`, empty if none have been recorded.
+    pub fn load(&self, year: u16) -> io::Result<YearAnswers> {
+        load_file(&self.path(year))
+    }
+
+    pub fn save(&self, year: u16, answers: &YearAnswers) -> io::Result<()> {
+        let toml = toml::to_string(answers).map_err(io::Error::other)?;
+        write_atomic(&self.path(year), &toml)
+    }
+
+    /// Stores `answer` as the right answer to `part` of `day`.
+    pub fn record(&self, year: u16, day: u8, part: u8, answer: Answer) -> io::Result<()> {
+        let mut answers = self.load(year)?;
+        answers.set(day, part, answer);
+        self.save(year, &answers)
+    }
+}
+
+fn load_file(path: &Path) -> io::Result<YearAnswers> {
+    match read_to_string(path) {
+        Ok(contents) => toml::from_str(&contents).map_err(io::Error::other),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(YearAnswers::default()),
+        Err(e) => Err(e),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    Wrong { expected: Answer },
+    Unsolved,
+    NoPuzzle,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Right => write!(f, "right"),
+            Self::Wrong { expected } => write!(f, "wrong (expected {expected})"),
+            Self::Unsolved => write!(f, "unsolved"),
+            Self::NoPuzzle => write!(f, "no puzzle"),
+        }
+    }
+}
+
+impl Verdict {
+    /// Whether nothing is known to be wrong.
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Self::Right | Self::NoPuzzle)
+    }
+
+    /// Checks `answer` against what is known about it.
+    pub fn check(expected: Expected, answer: &Answer) -> Self {
+        match (expected, answer) {
+            (Expected::Answer(expected), _) if expected == *answer => Self::Right,
+            (Expected::Answer(expected), _) => Self::Wrong { expected },
+            (Expected::NoPuzzle, _) | (Expected::Unknown, Answer::None) => Self::NoPuzzle,
+            (Expected::Unknown, _) => Self::Unsolved,
+        }
+    }
+
+    /// A short name for the verdict, without the expected answer.
+    pub fn status(&self) -> &'static str {
+        match self {
+            Self::Right => "right",
+            Self::Wrong { .. } => "wrong",
+            Self::Unsolved => "unsolved",
+            Self::NoPuzzle => "no puzzle",
+        }
+    }
+}
+
+/// Checks `answer` against the known answer for `part` of `day`.
+pub fn verify(year: u16, day: u8, part: u8, answer: &Answer) -> Verdict {
+    let answers = AnswerStore::from_env().load(year).unwrap_or_else(|e| {
+        eprintln!("couldn't load known answers for {year}: {e}");
+        YearAnswers::default()
+    });
+    Verdict::check(answers.expected(day, part), answer)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::Coordinate;
+
+    #[test]
+    fn checked_in_answers() {
+        let answers = AnswerStore::new("answers").load(2024).unwrap();
+        assert_eq!(
+            answers.expected(1, 1),
+            Expected::Answer(Answer::Int(1590491))
+        );
+        assert_eq!(
+            answers.expected(18, 2),
+            Expected::Answer(Answer::from(Coordinate::from_pathfinding((15, 20))))
+        );
+        assert_eq!(answers.expected(25, 2), Expected::NoPuzzle);
+        assert_eq!(
+            Verdict::check(Expected::Unknown, &Answer::None),
+            Verdict::NoPuzzle
+        );
+        assert!(matches!(
+            Verdict::check(answers.expected(1, 2), &Answer::None),
+            Verdict::Wrong { .. }
+        ));
+        assert_eq!(answers.expected(26, 1), Expected::Unknown);
+    }
+
+    #[test]
+    fn record_round_trip() {
+        let dir = env::temp_dir().join(format!("aoc-answers-{}", std::process::id()));
+        let store = AnswerStore::new(&dir);
+        assert_eq!(store.load(2023).unwrap(), YearAnswers::default());
+
+        store.record(2023, 3, 1, Answer::Int(7)).unwrap();
+        store
+            .record(
+                2023,
+                3,
+                2,
+                Answer::from(vec![Coordinate::from_pathfinding((1, 2))]),
+            )
+            .unwrap();
+        let answers = store.load(2023).unwrap();
+        assert_eq!(answers.expected(3, 1), Expected::Answer(Answer::Int(7)));
+        assert_eq!(
+            answers.expected(3, 2),
+            Expected::Answer(Answer::from("1,2"))
+        );
+        assert_eq!(answers.expected(4, 1), Expected::Unknown);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
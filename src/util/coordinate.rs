@@ -1,11 +1,75 @@
-use std::fmt::Display;
+use std::{
+    fmt::Display,
+    num::TryFromIntError,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+use exhaust::Exhaust;
+use serde::{Deserialize, Serialize};
+
+use super::direction::{Direction, Direction8};
+
+/// A cell of a [`grid::Grid`], in its `(row, col)` order: `x` is the row and `y` the column.
+///
+/// `pathfinding::grid::Grid` vertices are `(x, y)` the other way round, so convert them with
+/// [`Coordinate::from_pathfinding`] and [`Coordinate::to_pathfinding`] rather than `From`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Coordinate {
     pub x: usize,
     pub y: usize,
 }
 
+impl Coordinate {
+    pub const fn from_row_col(row: usize, col: usize) -> Self {
+        Self { x: row, y: col }
+    }
+
+    pub const fn row(self) -> usize {
+        self.x
+    }
+
+    pub const fn col(self) -> usize {
+        self.y
+    }
+
+    /// The cell at a `pathfinding::grid::Grid` vertex, which is `(column, row)`.
+    pub const fn from_pathfinding((x, y): (usize, usize)) -> Self {
+        Self::from_row_col(y, x)
+    }
+
+    /// This cell as a `pathfinding::grid::Grid` vertex, which is `(column, row)`.
+    pub const fn to_pathfinding(self) -> (usize, usize) {
+        (self.col(), self.row())
+    }
+
+    /// `self` moved by `offset`, or `None` if that would leave the non-negative quadrant.
+    pub fn checked_add(self, offset: CoordinateOffset) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_add_signed(offset.x)?,
+            y: self.y.checked_add_signed(offset.y)?,
+        })
+    }
+
+    pub fn manhattan(self, other: Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn chebyshev(self, other: Self) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// The orthogonally adjacent coordinates, leaving out any that would be negative.
+    pub fn neighbors4(self) -> impl Iterator<Item = Self> {
+        Direction::exhaust().filter_map(move |d| self.checked_add(d.to_delta()))
+    }
+
+    /// The orthogonally and diagonally adjacent coordinates, leaving out any that would be
+    /// negative.
+    pub fn neighbors8(self) -> impl Iterator<Item = Self> {
+        Direction8::exhaust().filter_map(move |d| self.checked_add(d.to_delta()))
+    }
+}
+
 impl Display for Coordinate {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "({}, {})", self.x, self.y)
@@ -39,12 +103,37 @@ impl From<&Coordinate> for (usize, usize) {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// A move between [`Coordinate`]s, in rows (`x`) and columns (`y`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct CoordinateOffset {
     pub x: isize,
     pub y: isize,
 }
 
+impl CoordinateOffset {
+    pub const ZERO: Self = Self { x: 0, y: 0 };
+
+    pub const fn from_row_col(rows: isize, cols: isize) -> Self {
+        Self { x: rows, y: cols }
+    }
+
+    pub const fn row(self) -> isize {
+        self.x
+    }
+
+    pub const fn col(self) -> isize {
+        self.y
+    }
+
+    pub fn manhattan(self) -> usize {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    pub fn chebyshev(self) -> usize {
+        self.x.unsigned_abs().max(self.y.unsigned_abs())
+    }
+}
+
 impl From<(isize, isize)> for CoordinateOffset {
     fn from(value: (isize, isize)) -> Self {
         Self {
@@ -54,15 +143,163 @@ impl From<(isize, isize)> for CoordinateOffset {
     }
 }
 
-impl From<CoordinateOffset> for Coordinate {
-    fn from(value: CoordinateOffset) -> Self {
+impl TryFrom<CoordinateOffset> for Coordinate {
+    type Error = TryFromIntError;
+
+    /// Fails if either axis is negative.
+    fn try_from(value: CoordinateOffset) -> Result<Self, Self::Error> {
+        Ok(Self {
+            x: value.x.try_into()?,
+            y: value.y.try_into()?,
+        })
+    }
+}
+
+impl Add<CoordinateOffset> for Coordinate {
+    type Output = Self;
+
+    /// Panics if the result would be negative; use [`Coordinate::checked_add`] when it might.
+    fn add(self, rhs: CoordinateOffset) -> Self::Output {
+        self.checked_add(rhs)
+            .unwrap_or_else(|| panic!("{self} + ({}, {}) is out of range", rhs.x, rhs.y))
+    }
+}
+
+impl AddAssign<CoordinateOffset> for Coordinate {
+    fn add_assign(&mut self, rhs: CoordinateOffset) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<CoordinateOffset> for Coordinate {
+    type Output = Self;
+
+    fn sub(self, rhs: CoordinateOffset) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl SubAssign<CoordinateOffset> for Coordinate {
+    fn sub_assign(&mut self, rhs: CoordinateOffset) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub for Coordinate {
+    type Output = CoordinateOffset;
+
+    /// The offset that moves `rhs` to `self`.
+    fn sub(self, rhs: Self) -> Self::Output {
+        CoordinateOffset {
+            x: self.x as isize - rhs.x as isize,
+            y: self.y as isize - rhs.y as isize,
+        }
+    }
+}
+
+impl Add for CoordinateOffset {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
         Self {
-            x: value.x as usize,
-            y: value.y as usize,
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
         }
     }
 }
 
-pub fn add_delta(cur: (usize, usize), delta: (isize, isize)) -> (isize, isize) {
-    (cur.0 as isize + delta.0, cur.1 as isize + delta.1)
+impl AddAssign for CoordinateOffset {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for CoordinateOffset {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl SubAssign for CoordinateOffset {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<isize> for CoordinateOffset {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+impl Neg for CoordinateOffset {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Coordinate::from((2, 5));
+        let b = Coordinate::from((4, 1));
+        let d = b - a;
+        assert_eq!(d, CoordinateOffset::from((2, -4)));
+        assert_eq!(a + d, b);
+        assert_eq!(b - d, a);
+        assert_eq!(a + d * -1, Coordinate::from((0, 9)));
+        assert_eq!(a.checked_add(d * 2), None);
+        assert_eq!(-d, a - b);
+        assert_eq!(a.manhattan(b), 6);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(d.manhattan(), 6);
+        assert_eq!(d.chebyshev(), 4);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let c = Coordinate::from_row_col(1, 4);
+        assert_eq!((c.row(), c.col()), (1, 4));
+        assert_eq!(c, Coordinate::from((1, 4)));
+        assert_eq!(c.to_pathfinding(), (4, 1));
+        assert_eq!(Coordinate::from_pathfinding((4, 1)), c);
+        assert_eq!(
+            Coordinate::from_pathfinding(c.to_pathfinding()),
+            c,
+            "pathfinding conversions round trip"
+        );
+
+        let down = CoordinateOffset::from_row_col(1, 0);
+        assert_eq!((down.row(), down.col()), (1, 0));
+        assert_eq!((c + down).row(), 2);
+    }
+
+    #[test]
+    fn neighbors() {
+        let origin = Coordinate::from((0, 0));
+        assert_eq!(
+            origin.neighbors4().collect::<Vec<_>>(),
+            [(1, 0), (0, 1)].map(Coordinate::from)
+        );
+        assert_eq!(origin.neighbors8().count(), 3);
+        let c = Coordinate::from((3, 3));
+        assert_eq!(c.neighbors4().count(), 4);
+        assert!(c.neighbors8().all(|n| c.chebyshev(n) == 1));
+        assert_eq!(c.neighbors8().filter(|&n| c.manhattan(n) == 1).count(), 4);
+    }
 }
@@ -2,7 +2,10 @@ use std::{cmp::Ordering, fmt::Display};
 
 use exhaust::Exhaust;
 
-use super::coordinate::{Coordinate, CoordinateOffset};
+use super::{
+    coordinate::{Coordinate, CoordinateOffset},
+    point::Point,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Exhaust)]
 pub enum Direction {
@@ -13,24 +16,20 @@ pub enum Direction {
 }
 
 impl Direction {
+    /// One step this way, where up and down change the row and left and right the column.
     pub fn to_delta(&self) -> CoordinateOffset {
-        match self {
-            Self::LEFT => (0, -1),
-            Self::RIGHT => (0, 1),
-            Self::UP => (-1, 0),
-            Self::DOWN => (1, 0),
-        }
-        .into()
+        Direction8::from(*self).to_delta()
     }
 
+    /// The direction from `a` to `b`, which must share a row or a column.
     pub fn from_coords(a: Coordinate, b: Coordinate) -> Self {
         assert!(
-            (a.x == b.x) ^ (a.y == b.y),
+            (a.row() == b.row()) ^ (a.col() == b.col()),
             "coordinates must be unique and lie on a cardinal line {},{}",
             a,
             b
         );
-        match (a.x.cmp(&b.x), a.y.cmp(&b.y)) {
+        match (a.row().cmp(&b.row()), a.col().cmp(&b.col())) {
             (_, Ordering::Less) => Self::RIGHT,
             (_, Ordering::Greater) => Self::LEFT,
             (Ordering::Less, _) => Self::DOWN,
@@ -39,13 +38,36 @@ impl Direction {
         }
     }
 
-    pub fn from_char(c: u8) -> Self {
+    /// Parses one of the arrows `<>^v` that [`Direction::to_char`] writes.
+    pub fn from_char(c: u8) -> Option<Self> {
         match c {
-            b'<' => Self::LEFT,
-            b'>' => Self::RIGHT,
-            b'^' => Self::UP,
-            b'v' => Self::DOWN,
-            _ => unreachable!(),
+            b'<' => Some(Self::LEFT),
+            b'>' => Some(Self::RIGHT),
+            b'^' => Some(Self::UP),
+            b'v' => Some(Self::DOWN),
+            _ => None,
+        }
+    }
+
+    /// Parses one of the letters `U`, `D`, `L` and `R`.
+    pub fn from_letter(c: u8) -> Option<Self> {
+        match c {
+            b'L' => Some(Self::LEFT),
+            b'R' => Some(Self::RIGHT),
+            b'U' => Some(Self::UP),
+            b'D' => Some(Self::DOWN),
+            _ => None,
+        }
+    }
+
+    /// Parses one of the compass points `N`, `S`, `E` and `W`, with north up.
+    pub fn from_compass(c: u8) -> Option<Self> {
+        match c {
+            b'W' => Some(Self::LEFT),
+            b'E' => Some(Self::RIGHT),
+            b'N' => Some(Self::UP),
+            b'S' => Some(Self::DOWN),
+            _ => None,
         }
     }
 
@@ -76,21 +98,28 @@ impl Direction {
         }
     }
 
-    pub fn cw_turns(&self, other: Self) -> usize {
-        (0..4)
-            .find_map(|n| {
-                let mut dir = *self;
-                for _ in 0..n {
-                    dir = dir.cw();
-                }
-                (dir == other).then_some(n)
-            })
-            .unwrap()
+    pub fn ccw(&self) -> Self {
+        match self {
+            Self::DOWN => Self::RIGHT,
+            Self::LEFT => Self::DOWN,
+            Self::UP => Self::LEFT,
+            Self::RIGHT => Self::UP,
+        }
     }
 
-    pub fn move_dir(&self, pos: Coordinate) -> CoordinateOffset {
-        let d = self.to_delta();
-        (pos.x as isize + d.x, pos.y as isize + d.y).into()
+    pub fn turn(&self, turn: Turn) -> Self {
+        match turn {
+            Turn::STRAIGHT => *self,
+            Turn::RIGHT => self.cw(),
+            Turn::BACK => self.opposite(),
+            Turn::LEFT => self.ccw(),
+        }
+    }
+
+    /// How many quarter turns clockwise it takes to face `other`.
+    pub fn cw_turns(&self, other: Self) -> usize {
+        // The variants are declared counter-clockwise.
+        (*self as usize + 4 - other as usize) % 4
     }
 }
 
@@ -108,3 +137,299 @@ impl Display for Direction {
         )
     }
 }
+
+/// A turn relative to the current facing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Exhaust)]
+pub enum Turn {
+    STRAIGHT,
+    RIGHT,
+    BACK,
+    LEFT,
+}
+
+impl Turn {
+    /// Parses `L` or `R`.
+    pub fn from_char(c: u8) -> Option<Self> {
+        match c {
+            b'L' => Some(Self::LEFT),
+            b'R' => Some(Self::RIGHT),
+            _ => None,
+        }
+    }
+}
+
+/// A position on a grid and the direction it faces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Heading {
+    pub pos: Coordinate,
+    pub dir: Direction,
+}
+
+impl Heading {
+    pub const fn new(pos: Coordinate, dir: Direction) -> Self {
+        Self { pos, dir }
+    }
+
+    /// One step forward, or `None` if that would leave the grid through row or column 0.
+    pub fn advance(self) -> Option<Self> {
+        Some(Self {
+            pos: self.pos.checked_add(self.dir.to_delta())?,
+            ..self
+        })
+    }
+
+    /// The same position facing a new way.
+    pub fn turn(self, turn: Turn) -> Self {
+        Self {
+            dir: self.dir.turn(turn),
+            ..self
+        }
+    }
+}
+
+impl<T> From<(T, Direction)> for Heading
+where
+    T: Into<Coordinate>,
+{
+    fn from(value: (T, Direction)) -> Self {
+        Self::new(value.0.into(), value.1)
+    }
+}
+
+impl Display for Heading {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.pos, self.dir)
+    }
+}
+
+/// One of the eight compass moves on a grid, listed clockwise from [`Direction8::UP`].
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Exhaust)]
+pub enum Direction8 {
+    UP,
+    UP_RIGHT,
+    RIGHT,
+    DOWN_RIGHT,
+    DOWN,
+    DOWN_LEFT,
+    LEFT,
+    UP_LEFT,
+}
+
+impl Direction8 {
+    /// The direction `n` eighths of a turn clockwise from this one.
+    fn rotate(&self, n: usize) -> Self {
+        Self::exhaust().cycle().nth(*self as usize + n).unwrap()
+    }
+
+    /// One step this way, where up and down change the row and left and right the column.
+    ///
+    /// Every neighbor offset in the crate comes from here, through [`Direction::to_delta`] for
+    /// the four cardinal ones.
+    pub fn to_delta(&self) -> CoordinateOffset {
+        match self {
+            Self::UP => (-1, 0),
+            Self::UP_RIGHT => (-1, 1),
+            Self::RIGHT => (0, 1),
+            Self::DOWN_RIGHT => (1, 1),
+            Self::DOWN => (1, 0),
+            Self::DOWN_LEFT => (1, -1),
+            Self::LEFT => (0, -1),
+            Self::UP_LEFT => (-1, -1),
+        }
+        .into()
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        *self as usize % 2 == 1
+    }
+
+    /// The matching 4-way direction, or `None` for the diagonals.
+    pub fn to_cardinal(&self) -> Option<Direction> {
+        match self {
+            Self::UP => Some(Direction::UP),
+            Self::RIGHT => Some(Direction::RIGHT),
+            Self::DOWN => Some(Direction::DOWN),
+            Self::LEFT => Some(Direction::LEFT),
+            _ => None,
+        }
+    }
+
+    pub fn opposite(&self) -> Self {
+        self.rotate(4)
+    }
+
+    pub fn cw45(&self) -> Self {
+        self.rotate(1)
+    }
+
+    pub fn ccw45(&self) -> Self {
+        self.rotate(7)
+    }
+
+    pub fn cw90(&self) -> Self {
+        self.rotate(2)
+    }
+
+    pub fn ccw90(&self) -> Self {
+        self.rotate(6)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(value: Direction) -> Self {
+        match value {
+            Direction::UP => Self::UP,
+            Direction::RIGHT => Self::RIGHT,
+            Direction::DOWN => Self::DOWN,
+            Direction::LEFT => Self::LEFT,
+        }
+    }
+}
+
+/// One of the six moves on a hex grid of flat-topped hexes, listed clockwise from
+/// [`HexDirection::N`], in axial coordinates where `x` grows to the east and `y` to the south.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Exhaust)]
+pub enum HexDirection {
+    N,
+    NE,
+    SE,
+    S,
+    SW,
+    NW,
+}
+
+impl HexDirection {
+    fn rotate(&self, n: usize) -> Self {
+        Self::exhaust().cycle().nth(*self as usize + n).unwrap()
+    }
+
+    pub fn to_delta(&self) -> Point<isize> {
+        match self {
+            Self::N => (0, -1),
+            Self::NE => (1, -1),
+            Self::SE => (1, 0),
+            Self::S => (0, 1),
+            Self::SW => (-1, 1),
+            Self::NW => (-1, 0),
+        }
+        .into()
+    }
+
+    pub fn opposite(&self) -> Self {
+        self.rotate(3)
+    }
+
+    pub fn cw(&self) -> Self {
+        self.rotate(1)
+    }
+
+    pub fn ccw(&self) -> Self {
+        self.rotate(5)
+    }
+
+    /// The number of moves between two axial positions.
+    pub fn distance(a: Point<isize>, b: Point<isize>) -> usize {
+        let d = b - a;
+        (d.x.unsigned_abs() + d.y.unsigned_abs() + (d.x + d.y).unsigned_abs()) / 2
+    }
+}
+
+impl Display for HexDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::N => "n",
+                Self::NE => "ne",
+                Self::SE => "se",
+                Self::S => "s",
+                Self::SW => "sw",
+                Self::NW => "nw",
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        for dir in Direction::exhaust() {
+            assert_eq!(dir.ccw().cw(), dir);
+            assert_eq!(dir.turn(Turn::BACK), dir.opposite());
+            for turn in Turn::exhaust() {
+                let turned = dir.turn(turn);
+                assert_eq!(dir.cw_turns(turned), turn as usize);
+                assert_eq!(Direction::from_char(turned.to_char()), Some(turned));
+            }
+        }
+        assert_eq!(Direction::UP.cw_turns(Direction::LEFT), 3);
+        assert_eq!(Direction::from_char(b'x'), None);
+        assert_eq!(Direction::from_letter(b'L'), Some(Direction::LEFT));
+        assert_eq!(Direction::from_compass(b'E'), Some(Direction::RIGHT));
+        assert_eq!(Direction::from_compass(b'L'), None);
+        assert_eq!(Turn::from_char(b'R'), Some(Turn::RIGHT));
+    }
+
+    #[test]
+    fn headings() {
+        let h = Heading::from(((1, 0), Direction::UP));
+        assert_eq!(h.advance(), Some(((0, 0), Direction::UP).into()));
+        assert_eq!(h.advance().and_then(Heading::advance), None);
+        let h = h.turn(Turn::LEFT);
+        assert_eq!(h.dir, Direction::LEFT);
+        assert_eq!(h.advance(), None);
+        assert_eq!(h.turn(Turn::BACK).advance().unwrap().pos, (1, 1).into());
+        assert_eq!(h.to_string(), "(1, 0) <");
+    }
+
+    #[test]
+    fn eight_way() {
+        let dirs = Direction8::exhaust().collect::<Vec<_>>();
+        assert_eq!(
+            dirs.iter().map(Direction8::cw45).collect::<Vec<_>>(),
+            [&dirs[1..], &dirs[..1]].concat()
+        );
+        assert_eq!(
+            Direction8::UP_RIGHT.to_delta(),
+            CoordinateOffset::from_row_col(-1, 1)
+        );
+        for dir in dirs {
+            assert_eq!(dir.cw45().ccw45(), dir);
+            assert_eq!(dir.cw90(), dir.cw45().cw45());
+            assert_eq!(dir.ccw90().opposite(), dir.cw90());
+            assert_eq!(dir.opposite().to_delta(), -dir.to_delta());
+            assert_eq!(dir.is_diagonal(), dir.to_cardinal().is_none());
+        }
+        for dir in Direction::exhaust() {
+            let dir8 = Direction8::from(dir);
+            assert_eq!(dir8.to_cardinal(), Some(dir));
+            assert_eq!(dir8.to_delta(), dir.to_delta());
+            assert_eq!(dir8.cw90(), dir.cw().into());
+        }
+    }
+
+    #[test]
+    fn hex() {
+        let origin = Point::new(0, 0);
+        for dir in HexDirection::exhaust() {
+            assert_eq!(dir.cw().ccw(), dir);
+            assert_eq!(dir.opposite().to_delta(), -dir.to_delta());
+            assert_eq!(HexDirection::distance(origin, dir.to_delta()), 1);
+        }
+        let path = [
+            HexDirection::NE,
+            HexDirection::NE,
+            HexDirection::S,
+            HexDirection::S,
+        ];
+        let end = path.iter().fold(origin, |p, d| p + d.to_delta());
+        assert_eq!(end, Point::new(2, 0));
+        assert_eq!(HexDirection::distance(origin, end), 2);
+        assert_eq!(HexDirection::N.to_string(), "n");
+    }
+}
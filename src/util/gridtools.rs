@@ -3,12 +3,13 @@ use std::{
     ops::{Deref, Index, IndexMut},
 };
 
+use exhaust::Exhaust;
 use grid::Grid;
 use itertools::Itertools;
 
 use super::{
-    coordinate::{add_delta, Coordinate, CoordinateOffset},
-    direction::Direction,
+    coordinate::{Coordinate, CoordinateOffset},
+    direction::{Direction, Direction8},
 };
 
 pub struct GridRef<'a, T: Sized> {
@@ -19,7 +20,7 @@ impl<T> Deref for GridRef<'_, T> {
     type Target = Grid<T>;
 
     fn deref(&self) -> &Self::Target {
-        &self.grid
+        self.grid
     }
 }
 
@@ -61,11 +62,8 @@ pub trait Gridtools<T: Sized> {
 
 impl<T> Gridtools<T> for Grid<T> {
     fn try_move_dir(&self, pos: Coordinate, dir: Direction) -> Option<Coordinate> {
-        let new_pos = dir.move_dir(pos);
-        self.is_valid_index(new_pos).then_some(Coordinate {
-            x: new_pos.x as usize,
-            y: new_pos.y as usize,
-        })
+        pos.checked_add(dir.to_delta())
+            .filter(|&new_pos| self.is_valid_index(new_pos))
     }
 }
 
@@ -87,13 +85,13 @@ impl<T: Sized> IsValidIndex<(usize, usize)> for Grid<T> {
 
 impl<T: Sized> IsValidIndex<CoordinateOffset> for Grid<T> {
     fn is_valid_index(&self, index: CoordinateOffset) -> bool {
-        index.x >= 0 && index.y >= 0 && self.is_valid_index((index.x as usize, index.y as usize))
+        Coordinate::try_from(index).is_ok_and(|index| self.is_valid_index(index))
     }
 }
 
 impl<T: Sized> IsValidIndex<Coordinate> for Grid<T> {
     fn is_valid_index(&self, index: Coordinate) -> bool {
-        index.x < self.rows() && index.y < self.cols()
+        index.row() < self.rows() && index.col() < self.cols()
     }
 }
 
@@ -101,38 +99,35 @@ impl<T: Sized> Index<Coordinate> for Grid<T> {
     type Output = T;
 
     fn index(&self, index: Coordinate) -> &Self::Output {
-        &self[(index.x, index.y)]
+        &self[(index.row(), index.col())]
     }
 }
 
 impl<T: Sized> IndexMut<Coordinate> for Grid<T> {
     fn index_mut(&mut self, index: Coordinate) -> &mut Self::Output {
-        &mut self[(index.x, index.y)]
+        &mut self[(index.row(), index.col())]
     }
 }
 
-const DELTAS: [(isize, isize); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
-
-const EIGHT_DELTAS: [(isize, isize); 8] = [
-    (-1, 0),
-    (-1, 1),
-    (0, 1),
-    (1, 1),
-    (1, 0),
-    (1, -1),
-    (0, -1),
-    (-1, -1),
-];
+/// A `pathfinding` grid with a vertex at every cell of `grid` that `is_open`, keeping the cells
+/// where they are: cell `(row, col)` becomes vertex `(col, row)`.
+pub fn pathfinding_grid<T>(
+    grid: &Grid<T>,
+    is_open: impl Fn(&T) -> bool,
+) -> pathfinding::grid::Grid {
+    let mut open = pathfinding::grid::Grid::new(grid.cols(), grid.rows());
+    for (pos, cell) in grid.indexed_iter() {
+        if is_open(cell) {
+            open.add_vertex(Coordinate::from(pos).to_pathfinding());
+        }
+    }
+    open
+}
 
 pub fn adj_squares<T: Sized>(grid: &Grid<T>, cur: (usize, usize)) -> Vec<(usize, usize)> {
-    let cur_i = (cur.0 as isize, cur.1 as isize);
-    DELTAS
-        .iter()
-        .filter_map(|(dx, dy)| {
-            let sq = (cur_i.0 + dx, cur_i.1 + dy);
-            grid.is_valid_index(sq)
-                .then_some((sq.0 as usize, sq.1 as usize))
-        })
+    Direction::exhaust()
+        .filter_map(|dir| grid.try_move_dir(cur.into(), dir))
+        .map(<(usize, usize)>::from)
         .collect_vec()
 }
 
@@ -148,46 +143,51 @@ pub struct Neighborhood {
 }
 
 pub fn adj_squares8(grid: &Grid<u8>, cur: (usize, usize)) -> Neighborhood {
+    let at = |dir: Direction8| {
+        let pos = Coordinate::from(cur).checked_add(dir.to_delta())?;
+        grid.get(pos.row(), pos.col()).copied()
+    };
     Neighborhood {
-        up: {
-            let pos = add_delta(cur, EIGHT_DELTAS[0]);
-            grid.is_valid_index(pos)
-                .then(|| *grid.get(pos.0, pos.1).unwrap())
-        },
-        up_right: {
-            let pos = add_delta(cur, EIGHT_DELTAS[1]);
-            grid.is_valid_index(pos)
-                .then(|| *grid.get(pos.0, pos.1).unwrap())
-        },
-        right: {
-            let pos = add_delta(cur, EIGHT_DELTAS[2]);
-            grid.is_valid_index(pos)
-                .then(|| *grid.get(pos.0, pos.1).unwrap())
-        },
-        down_right: {
-            let pos = add_delta(cur, EIGHT_DELTAS[3]);
-            grid.is_valid_index(pos)
-                .then(|| *grid.get(pos.0, pos.1).unwrap())
-        },
-        down: {
-            let pos = add_delta(cur, EIGHT_DELTAS[4]);
-            grid.is_valid_index(pos)
-                .then(|| *grid.get(pos.0, pos.1).unwrap())
-        },
-        down_left: {
-            let pos = add_delta(cur, EIGHT_DELTAS[5]);
-            grid.is_valid_index(pos)
-                .then(|| *grid.get(pos.0, pos.1).unwrap())
-        },
-        left: {
-            let pos = add_delta(cur, EIGHT_DELTAS[6]);
-            grid.is_valid_index(pos)
-                .then(|| *grid.get(pos.0, pos.1).unwrap())
-        },
-        up_left: {
-            let pos = add_delta(cur, EIGHT_DELTAS[7]);
-            grid.is_valid_index(pos)
-                .then(|| *grid.get(pos.0, pos.1).unwrap())
-        },
+        up: at(Direction8::UP),
+        up_right: at(Direction8::UP_RIGHT),
+        right: at(Direction8::RIGHT),
+        down_right: at(Direction8::DOWN_RIGHT),
+        down: at(Direction8::DOWN),
+        down_left: at(Direction8::DOWN_LEFT),
+        left: at(Direction8::LEFT),
+        up_left: at(Direction8::UP_LEFT),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn grid_and_pathfinding_agree() {
+        // Three rows of two columns, so mixing up rows and columns goes out of bounds.
+        let grid = Grid::from_vec(b"#..#.#".to_vec(), 2);
+        let open = pathfinding_grid(&grid, |&c| c == b'.');
+        assert_eq!((open.width, open.height), (grid.cols(), grid.rows()));
+        for (pos, &c) in grid.indexed_iter() {
+            let coord = Coordinate::from(pos);
+            assert_eq!(grid[coord], c);
+            assert_eq!(open.has_vertex(coord.to_pathfinding()), c == b'.');
+        }
+        let corner = Coordinate::from_row_col(2, 0);
+        assert!(grid.is_valid_index(corner));
+        assert!(!grid.is_valid_index(Coordinate::from_row_col(0, 2)));
+        assert_eq!(grid[corner], b'.');
+        assert_eq!(
+            Direction::from_coords(corner, Coordinate::from_row_col(1, 0)),
+            Direction::UP
+        );
+        assert_eq!(
+            grid.try_move_dir(corner, Direction::RIGHT),
+            Some((2, 1).into())
+        );
+        assert_eq!(grid.try_move_dir(corner, Direction::DOWN), None);
+        assert_eq!(adj_squares(&grid, (2, 0)), [(1, 0), (2, 1)]);
+        assert_eq!(adj_squares8(&grid, (2, 0)).up_right, Some(b'#'));
     }
 }
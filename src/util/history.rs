@@ -0,0 +1,194 @@
+use std::{collections::BTreeMap, env, io, path::PathBuf, process::Command, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use super::jsonl;
+
+/// File benchmark results are appended to, `bench_history.jsonl` by default.
+pub const HISTORY_ENV: &str = "AOC_BENCH_HISTORY";
+
+pub const PHASES: [&str; 3] = ["parse", "part1", "part2"];
+
+/// Median timings of one day at one commit, in nanoseconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Timings {
+    pub commit: String,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub year: u16,
+    pub day: u8,
+    pub parse: Option<f64>,
+    pub part1: Option<f64>,
+    pub part2: Option<f64>,
+}
+
+impl Timings {
+    pub fn phase(&self, phase: &str) -> Option<f64> {
+        match phase {
+            "parse" => self.parse,
+            "part1" => self.part1,
+            "part2" => self.part2,
+            _ => None,
+        }
+    }
+}
+
+/// The checked out commit, suffixed with `-dirty` when there are uncommitted changes.
+pub fn current_commit() -> Option<String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|out| out.status.success())
+            .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_string())
+    };
+    let commit = git(&["rev-parse", "--short", "HEAD"])?;
+    let dirty = git(&["status", "--porcelain", "--untracked-files=no"])
+        .is_some_and(|status| !status.is_empty());
+    Some(if dirty {
+        format!("{commit}-dirty")
+    } else {
+        commit
+    })
+}
+
+/// Append-only JSON Lines log of benchmark results.
+#[derive(Debug, Clone)]
+pub struct History {
+    path: PathBuf,
+}
+
+impl History {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The history at [`HISTORY_ENV`], or `bench_history.jsonl`.
+    pub fn from_env() -> Self {
+        Self::new(env::var(HISTORY_ENV).unwrap_or_else(|_| "bench_history.jsonl".to_string()))
+    }
+
+    pub fn record(&self, timings: &[Timings]) -> io::Result<()> {
+        jsonl::append(&self.path, timings)
+    }
+
+    /// Every recorded result, oldest first. A missing history has no results.
+    pub fn load(&self) -> io::Result<Vec<Timings>> {
+        jsonl::load(&self.path)
+    }
+}
+
+/// How one phase of one day changed between two commits.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub year: u16,
+    pub day: u8,
+    pub phase: &'static str,
+    pub before: Duration,
+    pub after: Duration,
+}
+
+impl Change {
+    /// `after` relative to `before`, so 1.5 means 50% slower.
+    pub fn ratio(&self) -> f64 {
+        self.after.as_secs_f64() / self.before.as_secs_f64()
+    }
+
+    /// Whether this got slower by more than `threshold` percent.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.ratio() > 1.0 + threshold / 100.0
+    }
+}
+
+/// Commits in the order they were first recorded.
+pub fn commits(history: &[Timings]) -> Vec<&str> {
+    let mut commits: Vec<&str> = Vec::new();
+    for t in history {
+        if !commits.contains(&t.commit.as_str()) {
+            commits.push(&t.commit);
+        }
+    }
+    commits
+}
+
+/// Compares the latest results recorded at `before` against those at `after`, for every phase
+/// both have.
+pub fn compare(history: &[Timings], before: &str, after: &str) -> Vec<Change> {
+    let latest = |commit: &str| {
+        history
+            .iter()
+            .filter(|t| t.commit == commit)
+            .map(|t| ((t.year, t.day), t))
+            .collect::<BTreeMap<_, _>>()
+    };
+    let (before, after) = (latest(before), latest(after));
+    let mut changes = Vec::new();
+    for (&(year, day), a) in &after {
+        let Some(b) = before.get(&(year, day)) else {
+            continue;
+        };
+        for phase in PHASES {
+            if let (Some(b), Some(a)) = (b.phase(phase), a.phase(phase)) {
+                changes.push(Change {
+                    year,
+                    day,
+                    phase,
+                    before: Duration::from_secs_f64(b / 1e9),
+                    after: Duration::from_secs_f64(a / 1e9),
+                });
+            }
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+
+    fn timings(commit: &str, day: u8, parse: f64, part1: f64) -> Timings {
+        Timings {
+            commit: commit.to_string(),
+            timestamp: 0,
+            year: 2024,
+            day,
+            parse: Some(parse),
+            part1: Some(part1),
+            part2: None,
+        }
+    }
+
+    #[test]
+    fn regressions() {
+        let history = [
+            timings("aaa", 1, 100.0, 1000.0),
+            timings("aaa", 2, 100.0, 1000.0),
+            timings("bbb", 1, 105.0, 2000.0),
+            timings("bbb", 2, 50.0, 1000.0),
+            timings("bbb", 3, 50.0, 1000.0),
+        ];
+        assert_eq!(commits(&history), ["aaa", "bbb"]);
+        let changes = compare(&history, "aaa", "bbb");
+        assert_eq!(changes.len(), 4);
+        let regressed = changes
+            .iter()
+            .filter(|c| c.is_regression(10.0))
+            .map(|c| (c.day, c.phase))
+            .collect::<Vec<_>>();
+        assert_eq!(regressed, [(1, "part1")]);
+    }
+
+    #[test]
+    fn round_trip() {
+        let dir = env::temp_dir().join(format!("aoc-history-{}", std::process::id()));
+        let history = History::new(dir.join("history.jsonl"));
+        assert!(history.load().unwrap().is_empty());
+        let recorded = [timings("aaa", 1, 1.5, 2.5), timings("aaa", 2, 3.0, 4.0)];
+        history.record(&recorded).unwrap();
+        assert_eq!(history.load().unwrap(), recorded);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,366 @@
+use std::{
+    collections::HashMap,
+    env,
+    error::Error,
+    fmt::Display,
+    fs::{self, read_to_string, File},
+    future::Future,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use reqwest::{header::COOKIE, StatusCode};
+
+use super::session::{session_cookie, session_file, to_cookie};
+
+/// Set to anything but `0` to never download inputs.
+pub const OFFLINE_ENV: &str = "AOC_OFFLINE";
+/// Directory inputs are cached in, `inputs` by default.
+pub const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
+/// Server inputs are downloaded from, `https://adventofcode.com` by default.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+#[derive(Debug)]
+pub enum InputError {
+    MissingCookie,
+    Offline { year: u16, day: u8 },
+    NotCached { path: PathBuf },
+    Http(StatusCode),
+    Request(reqwest::Error),
+    Io(io::Error),
+    EmptyBody,
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingCookie => {
+                write!(
+                    f,
+                    "no session cookie configured, pass --session or set AOC_SESSION"
+                )?;
+                match session_file() {
+                    Some(path) => write!(f, " or write it to {}", path.display()),
+                    None => Ok(()),
+                }
+            }
+            Self::Offline { year, day } => {
+                write!(
+                    f,
+                    "{year} day {day} is not cached and downloads are disabled"
+                )
+            }
+            Self::NotCached { path } => write!(f, "{} isn't cached", path.display()),
+            Self::Http(StatusCode::NOT_FOUND) => {
+                write!(
+                    f,
+                    "puzzle input not found (HTTP 404), is the day unlocked yet?"
+                )
+            }
+            Self::Http(StatusCode::BAD_REQUEST) => {
+                write!(f, "session cookie rejected (HTTP 400), is it expired?")
+            }
+            Self::Http(status) => write!(f, "unexpected response ({status})"),
+            Self::Request(e) => write!(f, "request failed: {e}"),
+            Self::Io(e) => write!(f, "input cache: {e}"),
+            Self::EmptyBody => write!(f, "received an empty puzzle input"),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Request(e) => Some(e),
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for InputError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<reqwest::Error> for InputError {
+    fn from(value: reqwest::Error) -> Self {
+        Self::Request(value)
+    }
+}
+
+/// Somewhere puzzle inputs can be loaded from.
+pub trait InputSource: Sync {
+    fn fetch(&self, year: u16, day: u8) -> impl Future<Output = Result<String, InputError>> + Send;
+}
+
+/// Replaces the contents of `path` by writing to a sibling file and renaming it into place, so
+/// readers never see a partial or stale file.
+pub(crate) fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("tmp");
+    let mut f = File::create(&tmp)?;
+    f.write_all(contents.as_bytes())?;
+    f.sync_all()?;
+    fs::rename(&tmp, path)
+}
+
+/// Inputs stored as `{dir}/{year}/day{day}.txt`.
+#[derive(Debug, Clone)]
+pub struct FsCache {
+    dir: PathBuf,
+}
+
+impl FsCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The cache at [`INPUT_DIR_ENV`], or `inputs`.
+    pub fn from_env() -> Self {
+        Self::new(env::var(INPUT_DIR_ENV).unwrap_or_else(|_| "inputs".to_string()))
+    }
+
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.dir
+            .join(year.to_string())
+            .join(format!("day{day}.txt"))
+    }
+
+    pub fn store(&self, year: u16, day: u8, input: &str) -> io::Result<()> {
+        write_atomic(&self.path(year, day), input)
+    }
+
+    /// Reads a cached input without going through the async [`InputSource`] interface.
+    pub fn read(&self, year: u16, day: u8) -> Result<String, InputError> {
+        let path = self.path(year, day);
+        match read_to_string(&path) {
+            Ok(input) => Ok(input),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Err(InputError::NotCached { path }),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+impl InputSource for FsCache {
+    async fn fetch(&self, year: u16, day: u8) -> Result<String, InputError> {
+        self.read(year, day)
+    }
+}
+
+/// Downloads inputs from adventofcode.com, or anything that serves the same paths.
+#[derive(Debug, Clone)]
+pub struct Http {
+    base_url: String,
+    session: Option<String>,
+}
+
+impl Http {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: None,
+        }
+    }
+
+    /// Sends `token` instead of the one resolved by [`session_cookie`].
+    pub fn with_session(mut self, token: &str) -> Self {
+        self.session = to_cookie(token);
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub(crate) fn cookie(&self) -> Result<String, InputError> {
+        self.session.clone().map_or_else(session_cookie, Ok)
+    }
+}
+
+impl Default for Http {
+    fn default() -> Self {
+        Self::new(DEFAULT_BASE_URL)
+    }
+}
+
+impl InputSource for Http {
+    async fn fetch(&self, year: u16, day: u8) -> Result<String, InputError> {
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        let response = reqwest::Client::new()
+            .get(url)
+            .header(COOKIE, self.cookie()?)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(InputError::Http(response.status()));
+        }
+        let data = response.text().await?;
+        if data.trim().is_empty() {
+            return Err(InputError::EmptyBody);
+        }
+        Ok(data)
+    }
+}
+
+/// Inputs held in memory, mostly for tests.
+#[derive(Debug, Clone, Default)]
+pub struct Fixtures(HashMap<(u16, u8), String>);
+
+impl Fixtures {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, year: u16, day: u8, input: impl Into<String>) -> Self {
+        self.0.insert((year, day), input.into());
+        self
+    }
+}
+
+impl InputSource for Fixtures {
+    async fn fetch(&self, year: u16, day: u8) -> Result<String, InputError> {
+        self.0
+            .get(&(year, day))
+            .cloned()
+            .ok_or(InputError::Offline { year, day })
+    }
+}
+
+/// Reads from `cache` first, falling back to `upstream` and saving what it returns. Without an
+/// upstream, inputs the cache doesn't have are an [`InputError::Offline`] error.
+#[derive(Debug, Clone)]
+pub struct Cached<S> {
+    cache: FsCache,
+    upstream: Option<S>,
+}
+
+impl<S> Cached<S> {
+    pub fn new(cache: FsCache, upstream: S) -> Self {
+        Self {
+            cache,
+            upstream: Some(upstream),
+        }
+    }
+
+    pub fn offline(cache: FsCache) -> Self {
+        Self {
+            cache,
+            upstream: None,
+        }
+    }
+}
+
+impl<S: InputSource> InputSource for Cached<S> {
+    async fn fetch(&self, year: u16, day: u8) -> Result<String, InputError> {
+        match self.cache.fetch(year, day).await {
+            Err(InputError::NotCached { .. }) => {}
+            res => return res,
+        }
+        let Some(upstream) = &self.upstream else {
+            return Err(InputError::Offline { year, day });
+        };
+        let data = upstream.fetch(year, day).await?;
+        self.cache.store(year, day, &data)?;
+        Ok(data)
+    }
+}
+
+/// Whether [`OFFLINE_ENV`] asks for downloads to be disabled.
+pub fn offline() -> bool {
+    env::var(OFFLINE_ENV).is_ok_and(|v| !v.is_empty() && v != "0")
+}
+
+/// The input cache, downloading from adventofcode.com unless offline, as configured by
+/// [`OFFLINE_ENV`], [`INPUT_DIR_ENV`] and [`BASE_URL_ENV`].
+pub fn default_source() -> Cached<Http> {
+    let cache = FsCache::from_env();
+    if offline() {
+        return Cached::offline(cache);
+    }
+    let http = env::var(BASE_URL_ENV).map_or_else(|_| Http::default(), Http::new);
+    Cached::new(cache, http)
+}
+
+pub async fn fetch_input(year: u16, day: u8) -> Result<String, InputError> {
+    default_source().fetch(year, day).await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::mock_server::MockServer;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("aoc-{name}-{}", std::process::id()))
+    }
+
+    #[test]
+    fn write_atomic_replaces_contents() {
+        let path = temp_dir("write-atomic").join("day1.txt");
+        write_atomic(&path, "a much longer input\n").unwrap();
+        write_atomic(&path, "short\n").unwrap();
+        assert_eq!(read_to_string(&path).unwrap(), "short\n");
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn fixtures() {
+        let source = Fixtures::new().with(2024, 1, "1 2");
+        assert_eq!(source.fetch(2024, 1).await.unwrap(), "1 2");
+        assert!(matches!(
+            source.fetch(2024, 2).await,
+            Err(InputError::Offline { year: 2024, day: 2 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn http_statuses() {
+        let server = MockServer::start(vec![
+            ("GET /2024/day/1/input", 200, "3 4\n"),
+            ("GET /2024/day/2/input", 200, ""),
+            ("GET /2024/day/3/input", 400, "bad session"),
+        ])
+        .await;
+        let http = Http::new(server.url()).with_session("abc");
+        assert_eq!(http.fetch(2024, 1).await.unwrap(), "3 4\n");
+        assert!(matches!(
+            http.fetch(2024, 2).await,
+            Err(InputError::EmptyBody)
+        ));
+        assert!(matches!(
+            http.fetch(2024, 3).await,
+            Err(InputError::Http(StatusCode::BAD_REQUEST))
+        ));
+        assert!(matches!(
+            http.fetch(2024, 4).await,
+            Err(InputError::Http(StatusCode::NOT_FOUND))
+        ));
+        assert!(server.requests()[0].contains("cookie: session=abc"));
+    }
+
+    #[tokio::test]
+    async fn cached_stores_downloads() {
+        let dir = temp_dir("cached");
+        let cache = FsCache::new(&dir);
+        assert!(matches!(
+            cache.fetch(2024, 5).await,
+            Err(InputError::NotCached { path }) if path == cache.path(2024, 5)
+        ));
+        let offline = Cached::<Fixtures>::offline(cache.clone());
+        assert!(matches!(
+            offline.fetch(2024, 5).await,
+            Err(InputError::Offline { .. })
+        ));
+
+        let online = Cached::new(cache.clone(), Fixtures::new().with(2024, 5, "5"));
+        assert_eq!(online.fetch(2024, 5).await.unwrap(), "5");
+        assert_eq!(offline.fetch(2024, 5).await.unwrap(), "5");
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,43 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Write},
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Seconds since the Unix epoch, for timestamping records.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Appends `records` to the JSON Lines file at `path`, one per line, creating it and its
+/// directory if needed.
+pub fn append<T: Serialize>(path: &Path, records: &[T]) -> io::Result<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    let mut f = File::options().create(true).append(true).open(path)?;
+    for record in records {
+        writeln!(f, "{}", serde_json::to_string(record)?)?;
+    }
+    Ok(())
+}
+
+/// Every record in the JSON Lines file at `path`, oldest first, skipping blank lines. A missing
+/// file has no records.
+pub fn load<T: DeserializeOwned>(path: &Path) -> io::Result<Vec<T>> {
+    let f = match File::open(path) {
+        Ok(f) => f,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    BufReader::new(f)
+        .lines()
+        .filter(|l| l.as_ref().is_ok_and(|l| !l.trim().is_empty()))
+        .map(|l| Ok(serde_json::from_str(&l?)?))
+        .collect()
+}
@@ -0,0 +1,211 @@
+use std::{env, fmt::Display, io, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    answer::Answer,
+    jsonl,
+    submit::{Hint, SubmitOutcome},
+};
+
+/// File submissions are recorded in, `answers/ledger.jsonl` by default.
+pub const LEDGER_ENV: &str = "AOC_LEDGER";
+
+/// One answer sent to the site and what it said about it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: Answer,
+    pub outcome: SubmitOutcome,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+impl Attempt {
+    pub fn now(year: u16, day: u8, part: u8, answer: Answer, outcome: SubmitOutcome) -> Self {
+        Self {
+            year,
+            day,
+            part,
+            answer,
+            outcome,
+            timestamp: jsonl::now(),
+        }
+    }
+}
+
+/// What earlier wrong answers to one part say about the right one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bounds {
+    /// The right answer is greater than this.
+    pub above: Option<i128>,
+    /// The right answer is less than this.
+    pub below: Option<i128>,
+    pub wrong: Vec<Answer>,
+}
+
+/// Why a submission was refused without sending it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rejection {
+    TooLow { bound: i128 },
+    TooHigh { bound: i128 },
+    KnownWrong,
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooLow { bound } => write!(f, "answer must be greater than {bound}"),
+            Self::TooHigh { bound } => write!(f, "answer must be less than {bound}"),
+            Self::KnownWrong => write!(f, "answer was already submitted and was wrong"),
+        }
+    }
+}
+
+impl Bounds {
+    /// Narrows the bounds with every wrong answer in `attempts`, which should all be for the
+    /// same part.
+    pub fn from_attempts<'a>(attempts: impl IntoIterator<Item = &'a Attempt>) -> Self {
+        let mut bounds = Self::default();
+        for attempt in attempts {
+            let SubmitOutcome::Incorrect(hint) = attempt.outcome else {
+                continue;
+            };
+            match (hint, attempt.answer.as_number()) {
+                (Some(Hint::TooLow), Some(n)) => {
+                    bounds.above = bounds.above.max(Some(n));
+                }
+                (Some(Hint::TooHigh), Some(n)) => {
+                    bounds.below = Some(bounds.below.map_or(n, |b| b.min(n)));
+                }
+                _ => {}
+            }
+            if !bounds.wrong.contains(&attempt.answer) {
+                bounds.wrong.push(attempt.answer.clone());
+            }
+        }
+        bounds
+    }
+
+    /// Whether `answer` could still be right.
+    pub fn check(&self, answer: &Answer) -> Result<(), Rejection> {
+        if let Some(n) = answer.as_number() {
+            match (self.above, self.below) {
+                (Some(bound), _) if n <= bound => return Err(Rejection::TooLow { bound }),
+                (_, Some(bound)) if n >= bound => return Err(Rejection::TooHigh { bound }),
+                _ => {}
+            }
+        }
+        if self.wrong.contains(answer) {
+            return Err(Rejection::KnownWrong);
+        }
+        Ok(())
+    }
+}
+
+/// Append-only JSON Lines log of every submission.
+#[derive(Debug, Clone)]
+pub struct Ledger {
+    path: PathBuf,
+}
+
+impl Ledger {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The ledger at [`LEDGER_ENV`], or `answers/ledger.jsonl`.
+    pub fn from_env() -> Self {
+        Self::new(env::var(LEDGER_ENV).unwrap_or_else(|_| "answers/ledger.jsonl".to_string()))
+    }
+
+    pub fn record(&self, attempt: &Attempt) -> io::Result<()> {
+        jsonl::append(&self.path, std::slice::from_ref(attempt))
+    }
+
+    /// Every recorded attempt, oldest first. A missing ledger has no attempts.
+    pub fn attempts(&self) -> io::Result<Vec<Attempt>> {
+        jsonl::load(&self.path)
+    }
+
+    /// The bounds established by earlier attempts at `part` of `day`.
+    pub fn bounds(&self, year: u16, day: u8, part: u8) -> io::Result<Bounds> {
+        let attempts = self.attempts()?;
+        Ok(Bounds::from_attempts(
+            attempts
+                .iter()
+                .filter(|a| (a.year, a.day, a.part) == (year, day, part)),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, time::Duration};
+
+    use super::*;
+    use crate::util::Hint;
+
+    #[test]
+    fn round_trip() {
+        let dir = env::temp_dir().join(format!("aoc-ledger-{}", std::process::id()));
+        let ledger = Ledger::new(dir.join("ledger.jsonl"));
+        assert!(ledger.attempts().unwrap().is_empty());
+
+        let attempts = [
+            Attempt::now(
+                2024,
+                1,
+                1,
+                10u64.into(),
+                SubmitOutcome::Incorrect(Some(Hint::TooLow)),
+            ),
+            Attempt::now(
+                2024,
+                1,
+                1,
+                12u64.into(),
+                SubmitOutcome::RateLimited(Duration::from_secs(30)),
+            ),
+            Attempt::now(2024, 17, 1, "1,2,3".into(), SubmitOutcome::Correct),
+        ];
+        for attempt in &attempts {
+            ledger.record(attempt).unwrap();
+        }
+        assert_eq!(ledger.attempts().unwrap(), attempts);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn bounds() {
+        let wrong =
+            |answer: Answer, hint| Attempt::now(2024, 1, 1, answer, SubmitOutcome::Incorrect(hint));
+        let attempts = [
+            wrong(10u64.into(), Some(Hint::TooLow)),
+            wrong(50u64.into(), Some(Hint::TooHigh)),
+            wrong(20u64.into(), Some(Hint::TooLow)),
+            wrong(30u64.into(), None),
+            wrong(40u64.into(), Some(Hint::TooHigh)),
+        ];
+        let bounds = Bounds::from_attempts(&attempts);
+        assert_eq!((bounds.above, bounds.below), (Some(20), Some(40)));
+
+        assert_eq!(bounds.check(&25u64.into()), Ok(()));
+        assert_eq!(
+            bounds.check(&20u64.into()),
+            Err(Rejection::TooLow { bound: 20 })
+        );
+        assert_eq!(
+            bounds.check(&(-3i64).into()),
+            Err(Rejection::TooLow { bound: 20 })
+        );
+        assert_eq!(
+            bounds.check(&45u64.into()),
+            Err(Rejection::TooHigh { bound: 40 })
+        );
+        assert_eq!(bounds.check(&30u64.into()), Err(Rejection::KnownWrong));
+        assert_eq!(bounds.check(&"abc".into()), Ok(()));
+    }
+}
@@ -0,0 +1,75 @@
+//! A minimal HTTP server standing in for adventofcode.com in tests.
+
+use std::sync::{Arc, Mutex};
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+pub(crate) struct MockServer {
+    url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl MockServer {
+    /// Serves each `(request line prefix, status, body)` route, answering 404 to anything else.
+    pub(crate) async fn start(routes: Vec<(&'static str, u16, &'static str)>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let seen = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let request = read_request(&mut stream).await;
+                let (status, body) = routes
+                    .iter()
+                    .find(|(route, _, _)| request.starts_with(&format!("{route} ")))
+                    .map_or((404, ""), |&(_, status, body)| (status, body));
+                seen.lock().unwrap().push(request);
+                let response = format!(
+                    "HTTP/1.1 {status} Mock\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        Self { url, requests }
+    }
+
+    pub(crate) fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Every request received so far, headers lowercased.
+    pub(crate) fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+async fn read_request(stream: &mut tokio::net::TcpStream) -> String {
+    let mut buf = Vec::new();
+    let mut chunk = [0; 1024];
+    loop {
+        let n = stream.read(&mut chunk).await.unwrap_or(0);
+        buf.extend_from_slice(&chunk[..n]);
+        let text = String::from_utf8_lossy(&buf);
+        if let Some(end) = text.find("\r\n\r\n") {
+            let length = text[..end]
+                .lines()
+                .find_map(|l| {
+                    l.to_lowercase()
+                        .strip_prefix("content-length:")
+                        .map(|v| v.trim().parse().unwrap_or(0))
+                })
+                .unwrap_or(0);
+            if n == 0 || buf.len() >= end + 4 + length {
+                let (head, body) = text.split_at(end);
+                let (line, headers) = head.split_once("\r\n").unwrap_or((head, ""));
+                return format!("{line}\r\n{}{body}", headers.to_lowercase());
+            }
+        } else if n == 0 {
+            return text.into_owned();
+        }
+    }
+}
@@ -1,14 +1,40 @@
+pub mod alloc;
+pub mod answer;
+pub mod answers;
 pub mod coordinate;
 pub mod direction;
 pub mod gridtools;
+pub mod history;
+pub mod input;
 pub mod iter;
+pub mod jsonl;
+pub mod ledger;
+#[cfg(test)]
+pub(crate) mod mock_server;
+pub mod parse;
+pub mod point;
+pub mod puzzle;
+pub mod scaffold;
+pub mod session;
 pub mod submit;
+pub mod watch;
 
+pub use alloc::*;
+pub use answer::*;
+pub use answers::*;
 pub use coordinate::*;
 pub use direction::*;
 pub use gridtools::*;
+pub use input::*;
 pub use iter::*;
+pub use ledger::*;
+pub use parse::*;
+pub use point::*;
+pub use puzzle::*;
+pub use scaffold::*;
+pub use session::*;
 pub use submit::*;
+pub use watch::*;
 
 pub fn diff(c: u8, sq: Option<u8>) -> bool {
     sq.is_none_or(|x| x != c)
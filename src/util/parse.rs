@@ -0,0 +1,243 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use grid::Grid;
+
+/// Why an input couldn't be parsed, and where. Lines and columns count from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Helpers for parsing an input that report failures as [`ParseError`]s.
+///
+/// Everything works on slices of the input the parser was made with, so an error about a slice
+/// can be pinned to the line and column it came from.
+#[derive(Debug, Clone, Copy)]
+pub struct Parser<'a> {
+    input: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self { input }
+    }
+
+    pub fn input(&self) -> &'a str {
+        self.input
+    }
+
+    /// The line and column where `part` starts, or the start of the input if `part` isn't a
+    /// slice of it.
+    pub fn locate(&self, part: &str) -> (usize, usize) {
+        let offset = (part.as_ptr() as usize)
+            .checked_sub(self.input.as_ptr() as usize)
+            .filter(|&offset| offset <= self.input.len())
+            .unwrap_or(0);
+        let before = &self.input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        (
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+        )
+    }
+
+    /// An error about `part`, a slice of the input.
+    pub fn error(&self, part: &str, message: impl Into<String>) -> ParseError {
+        let (line, column) = self.locate(part);
+        ParseError::new(line, column, message)
+    }
+
+    /// An error for `s`, a slice of the input, ending before `what`.
+    pub fn missing(&self, s: &str, what: &str) -> ParseError {
+        self.error(&s[s.len()..], format!("expected {what}"))
+    }
+
+    pub fn num<T: FromStr>(&self, s: &str) -> Result<T, ParseError> {
+        s.parse()
+            .map_err(|_| self.error(s, format!("expected a number, found `{s}`")))
+    }
+
+    /// Numbers separated by whitespace.
+    pub fn words<T: FromStr>(&self, s: &str) -> Result<Vec<T>, ParseError> {
+        s.split_ascii_whitespace().map(|n| self.num(n)).collect()
+    }
+
+    /// Numbers separated by `sep`.
+    pub fn nums<T: FromStr>(&self, s: &str, sep: &str) -> Result<Vec<T>, ParseError> {
+        s.split(sep).map(|n| self.num(n)).collect()
+    }
+
+    /// Exactly two numbers separated by `sep`.
+    pub fn pair<T: FromStr>(&self, s: &str, sep: &str) -> Result<(T, T), ParseError> {
+        let (a, b) = self.split_once(s, sep)?;
+        Ok((self.num(a)?, self.num(b)?))
+    }
+
+    pub fn strip_prefix(&self, s: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        s.strip_prefix(prefix)
+            .ok_or_else(|| self.error(s, format!("expected `{prefix}`")))
+    }
+
+    pub fn split_once(&self, s: &'a str, sep: &str) -> Result<(&'a str, &'a str), ParseError> {
+        s.split_once(sep)
+            .ok_or_else(|| self.error(s, format!("expected `{sep}` in `{s}`")))
+    }
+
+    /// The next item of `items`, split from `s`, or an error that `s` ends before `what`.
+    pub fn next<T>(
+        &self,
+        items: &mut impl Iterator<Item = T>,
+        s: &str,
+        what: &str,
+    ) -> Result<T, ParseError> {
+        items.next().ok_or_else(|| self.missing(s, what))
+    }
+
+    /// Checks that every character of `s` apart from line breaks is one of the ASCII `allowed`.
+    pub fn only(&self, s: &str, allowed: &[u8], what: &str) -> Result<(), ParseError> {
+        let allowed = |c: char| c.is_ascii() && allowed.contains(&(c as u8));
+        let bad = s
+            .char_indices()
+            .find(|&(_, c)| !matches!(c, '\n' | '\r') && !allowed(c));
+        match bad {
+            Some((i, c)) => Err(self.error(&s[i..], format!("expected {what}, found `{c}`"))),
+            None => Ok(()),
+        }
+    }
+
+    /// The lines of `s` as a grid of bytes, checking every line is as long as the first.
+    pub fn grid(&self, s: &'a str) -> Result<Grid<u8>, ParseError> {
+        let width = s.lines().next().map_or(0, str::len);
+        if width == 0 {
+            return Err(self.error(s, "expected a grid"));
+        }
+        let mut cells = Vec::with_capacity(s.len());
+        for line in s.lines() {
+            if line.len() != width {
+                return Err(self.error(
+                    line,
+                    format!("expected {width} columns, found {}", line.len()),
+                ));
+            }
+            cells.extend_from_slice(line.as_bytes());
+        }
+        Ok(Grid::from_vec(cells, width))
+    }
+
+    /// Checks that the outermost rows and columns of a grid parsed from `s` by [`Parser::grid`]
+    /// are all `wall`, so nothing inside can step off it.
+    pub fn walled(&self, s: &str, grid: &Grid<u8>, wall: u8) -> Result<(), ParseError> {
+        let (rows, cols) = grid.size();
+        let gap = grid.indexed_iter().find(|&((row, col), &c)| {
+            (row == 0 || col == 0 || row == rows - 1 || col == cols - 1) && c != wall
+        });
+        match gap {
+            Some(((row, col), &c)) => {
+                let line = s.lines().nth(row).unwrap_or(s);
+                Err(self.error(
+                    line.get(col..).unwrap_or(line),
+                    format!(
+                        "expected `{}` around the grid, found `{}`",
+                        wall as char,
+                        String::from_utf8_lossy(&[c])
+                    ),
+                ))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// The position of the first `target` in a grid parsed from `s` by [`Parser::grid`].
+    pub fn find(
+        &self,
+        s: &'a str,
+        grid: &Grid<u8>,
+        target: u8,
+    ) -> Result<(usize, usize), ParseError> {
+        grid.indexed_iter()
+            .find_map(|(pos, &c)| (c == target).then_some(pos))
+            .ok_or_else(|| self.error(s, format!("no `{}` in the grid", target as char)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn locates_errors() {
+        let input = "1,2\n3,x\néé 4";
+        let p = Parser::new(input);
+        let lines = input.lines().collect::<Vec<_>>();
+        assert_eq!(p.pair::<u8>(lines[0], ","), Ok((1, 2)));
+        assert_eq!(
+            p.pair::<u8>(lines[1], ","),
+            Err(ParseError::new(2, 3, "expected a number, found `x`"))
+        );
+        assert_eq!(
+            p.split_once(lines[2], ",").unwrap_err().to_string(),
+            "line 3, column 1: expected `,` in `éé 4`"
+        );
+        assert_eq!(p.locate(&lines[2][5..]), (3, 4));
+        assert_eq!(
+            p.next(&mut lines.iter().skip(3), input, "a fourth line"),
+            Err(ParseError::new(3, 5, "expected a fourth line"))
+        );
+        assert_eq!(p.locate("elsewhere"), (1, 1));
+    }
+
+    #[test]
+    fn grids() {
+        let p = Parser::new("#.#\n.S.\n##");
+        assert_eq!(
+            p.grid(p.input()),
+            Err(ParseError::new(3, 1, "expected 3 columns, found 2"))
+        );
+        let input = "#.#\n.S.";
+        let p = Parser::new(input);
+        let grid = p.grid(input).unwrap();
+        assert_eq!(grid.size(), (2, 3));
+        assert_eq!(p.find(input, &grid, b'S'), Ok((1, 1)));
+        assert!(p.find(input, &grid, b'E').is_err());
+        assert_eq!(p.only(input, b"#.S", "a tile"), Ok(()));
+        let moves = "<>\n^é";
+        assert_eq!(
+            Parser::new(moves).only(moves, b"<>^v", "a move"),
+            Err(ParseError::new(2, 2, "expected a move, found `é`"))
+        );
+        let open = "#.#\n###";
+        let p = Parser::new(open);
+        assert_eq!(
+            p.walled(open, &p.grid(open).unwrap(), b'#'),
+            Err(ParseError::new(
+                1,
+                2,
+                "expected `#` around the grid, found `.`"
+            ))
+        );
+    }
+}
@@ -0,0 +1,374 @@
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+use exhaust::Exhaust;
+use num::{traits::Euclid, PrimInt};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    coordinate::{Coordinate, CoordinateOffset},
+    direction::{Direction, Direction8},
+};
+
+/// A 2D point or offset over any integer type, for puzzles where [`Coordinate`]'s `usize` is the
+/// wrong width or sign.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A 3D point or offset over any integer type.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// Integers whose distance apart is measured in the unsigned type of the same width, which
+/// holds it even between the signed extremes.
+pub trait AbsDiff: PrimInt {
+    type Unsigned: PrimInt;
+
+    fn abs_diff(self, other: Self) -> Self::Unsigned;
+}
+
+macro_rules! impl_abs_diff {
+    ( $( $t:ty => $u:ty ),* ) => {
+        $(
+            impl AbsDiff for $t {
+                type Unsigned = $u;
+
+                fn abs_diff(self, other: Self) -> $u {
+                    <$t>::abs_diff(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_abs_diff!(
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize,
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize
+);
+
+/// The sum of per-axis distances. Panics if it overflows `U`, which takes points more than half
+/// of the axis type's range apart on every axis.
+fn sum_distances<U: PrimInt>(distances: impl IntoIterator<Item = U>) -> U {
+    distances
+        .into_iter()
+        .try_fold(U::zero(), |sum, d| sum.checked_add(&d))
+        .expect("manhattan distance overflows")
+}
+
+/// `v` moved one step in the direction of `d`'s sign, if that stays in `T`'s range.
+fn step<T: PrimInt>(v: T, d: isize) -> Option<T> {
+    match d.signum() {
+        -1 => v.checked_sub(&T::one()),
+        1 => v.checked_add(&T::one()),
+        _ => Some(v),
+    }
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// Converts each axis to `U`, failing instead of wrapping if either doesn't fit.
+    pub fn try_cast<U: TryFrom<T>>(self) -> Result<Point<U>, U::Error> {
+        Ok(Point {
+            x: self.x.try_into()?,
+            y: self.y.try_into()?,
+        })
+    }
+
+    /// Converts each axis to a type that can hold every value of `T`.
+    pub fn cast<U: From<T>>(self) -> Point<U> {
+        Point {
+            x: self.x.into(),
+            y: self.y.into(),
+        }
+    }
+}
+
+impl<T: PrimInt> Point<T> {
+    /// `self + rhs`, or `None` if either axis overflows.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_add(&rhs.x)?,
+            y: self.y.checked_add(&rhs.y)?,
+        })
+    }
+
+    /// `self - rhs`, or `None` if either axis overflows, as it does below zero for unsigned `T`.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_sub(&rhs.x)?,
+            y: self.y.checked_sub(&rhs.y)?,
+        })
+    }
+
+    fn offsets(self, deltas: impl Iterator<Item = CoordinateOffset>) -> impl Iterator<Item = Self> {
+        deltas.filter_map(move |d| Some(Self::new(step(self.x, d.x)?, step(self.y, d.y)?)))
+    }
+
+    /// The orthogonally adjacent points that fit in `T`.
+    pub fn neighbors4(self) -> impl Iterator<Item = Self> {
+        self.offsets(Direction::exhaust().map(|d| d.to_delta()))
+    }
+
+    /// The orthogonally and diagonally adjacent points that fit in `T`.
+    pub fn neighbors8(self) -> impl Iterator<Item = Self> {
+        self.offsets(Direction8::exhaust().map(|d| d.to_delta()))
+    }
+}
+
+impl<T: AbsDiff> Point<T> {
+    /// Panics if the distance doesn't fit `T::Unsigned`.
+    pub fn manhattan(self, other: Self) -> T::Unsigned {
+        sum_distances([self.x.abs_diff(other.x), self.y.abs_diff(other.y)])
+    }
+
+    pub fn chebyshev(self, other: Self) -> T::Unsigned {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+}
+
+impl<T: PrimInt + Euclid> Point<T> {
+    /// Wraps `self` onto a torus of `size`, so every axis ends up in `0..size`.
+    pub fn rem_euclid(self, size: Self) -> Self {
+        Self {
+            x: self.x.rem_euclid(&size.x),
+            y: self.y.rem_euclid(&size.y),
+        }
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Converts each axis to `U`, failing instead of wrapping if any doesn't fit.
+    pub fn try_cast<U: TryFrom<T>>(self) -> Result<Point3<U>, U::Error> {
+        Ok(Point3 {
+            x: self.x.try_into()?,
+            y: self.y.try_into()?,
+            z: self.z.try_into()?,
+        })
+    }
+
+    /// Converts each axis to a type that can hold every value of `T`.
+    pub fn cast<U: From<T>>(self) -> Point3<U> {
+        Point3 {
+            x: self.x.into(),
+            y: self.y.into(),
+            z: self.z.into(),
+        }
+    }
+}
+
+impl<T: AbsDiff> Point3<T> {
+    /// Panics if the distance doesn't fit `T::Unsigned`.
+    pub fn manhattan(self, other: Self) -> T::Unsigned {
+        sum_distances([
+            self.x.abs_diff(other.x),
+            self.y.abs_diff(other.y),
+            self.z.abs_diff(other.z),
+        ])
+    }
+
+    pub fn chebyshev(self, other: Self) -> T::Unsigned {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+/// Componentwise operators, plus scaling by `T`.
+macro_rules! point_ops {
+    ($point:ident { $( $axis:ident ),* }) => {
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                Self { $( $axis: self.$axis + rhs.$axis ),* }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self { $( $axis: self.$axis - rhs.$axis ),* }
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self::Output {
+                Self { $( $axis: self.$axis * rhs ),* }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                Self { $( $axis: -self.$axis ),* }
+            }
+        }
+
+        impl<T: Copy + Add<Output = T>> AddAssign for $point<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<T: Copy + Sub<Output = T>> SubAssign for $point<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+    };
+}
+
+point_ops!(Point { x, y });
+point_ops!(Point3 { x, y, z });
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(value: Point<T>) -> Self {
+        (value.x, value.y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(value: Point3<T>) -> Self {
+        (value.x, value.y, value.z)
+    }
+}
+
+impl From<Coordinate> for Point<usize> {
+    fn from(value: Coordinate) -> Self {
+        Self::new(value.x, value.y)
+    }
+}
+
+impl From<Point<usize>> for Coordinate {
+    fn from(value: Point<usize>) -> Self {
+        Self {
+            x: value.x,
+            y: value.y,
+        }
+    }
+}
+
+impl From<CoordinateOffset> for Point<isize> {
+    fn from(value: CoordinateOffset) -> Self {
+        Self::new(value.x, value.y)
+    }
+}
+
+impl From<Point<isize>> for CoordinateOffset {
+    fn from(value: Point<isize>) -> Self {
+        Self {
+            x: value.x,
+            y: value.y,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn signed_points() {
+        let a = Point::<i16>::new(3, -2);
+        let b = Point::new(-1, 4);
+        assert_eq!(a + b, Point::new(2, 2));
+        assert_eq!(a - b, Point::new(4, -6));
+        assert_eq!(-a * 2, Point::new(-6, 4));
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(a.chebyshev(b), 6);
+        let (min, max) = (Point::new(i16::MIN, 0), Point::new(i16::MAX, 0));
+        assert_eq!(min.manhattan(max), u16::MAX);
+        assert_eq!(min.chebyshev(max), u16::MAX);
+        assert_eq!((a + b * 3).rem_euclid(Point::new(11, 7)), Point::new(0, 3));
+        assert_eq!(Point::<i8>::new(127, 0).checked_add(Point::new(1, 0)), None);
+    }
+
+    #[test]
+    fn conversions() {
+        let p = Point::<i16>::new(3, -2);
+        assert!(p.try_cast::<usize>().is_err());
+        assert_eq!(
+            Point::<i16>::new(3, 2).try_cast::<usize>(),
+            Ok(Point::new(3, 2))
+        );
+        assert_eq!(p.cast::<i64>(), Point::new(3i64, -2));
+        assert!(Point::<i64>::new(1 << 40, 0).try_cast::<i32>().is_err());
+
+        let c = Coordinate::from((1, 2));
+        assert_eq!(Coordinate::from(Point::from(c)), c);
+        assert!(Coordinate::try_from(CoordinateOffset::from((-1, 2))).is_err());
+        assert_eq!(Coordinate::try_from(CoordinateOffset::from((1, 2))), Ok(c));
+    }
+
+    #[test]
+    fn unsigned_neighbors() {
+        let origin = Point::<u8>::new(0, 0);
+        assert_eq!(
+            origin.neighbors4().collect::<Vec<_>>(),
+            [Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(origin.neighbors8().count(), 3);
+        assert_eq!(Point::<i32>::new(0, 0).neighbors8().count(), 8);
+        assert_eq!(Point::<u8>::new(255, 1).neighbors4().count(), 3);
+    }
+
+    #[test]
+    fn points_3d() {
+        let a = Point3::new(1i64, 2, 3);
+        let b = Point3::from((-4, 0, 9));
+        assert_eq!(a + b, Point3::new(-3, 2, 12));
+        assert_eq!(a.manhattan(b), 13);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!((a - b).try_cast::<u32>().ok(), None);
+        assert_eq!(a.try_cast::<u8>(), Ok(Point3::new(1, 2, 3)));
+    }
+}
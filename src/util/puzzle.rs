@@ -0,0 +1,282 @@
+use std::{env, fs::read_to_string, io, path::PathBuf};
+
+use reqwest::header::COOKIE;
+use scraper::{node::Node, ElementRef, Html, Selector};
+
+use super::input::{offline, write_atomic, Http, InputError, BASE_URL_ENV};
+
+/// Directory puzzle descriptions are cached in, `puzzles` by default.
+pub const PUZZLE_DIR_ENV: &str = "AOC_PUZZLE_DIR";
+
+/// A day's puzzle text and the example inputs it contains.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Puzzle {
+    pub markdown: String,
+    pub examples: Vec<String>,
+}
+
+impl Puzzle {
+    /// Extracts every `<article>` of a puzzle page and the `<pre><code>` blocks inside them.
+    pub fn from_html(html: &str) -> Self {
+        let document = Html::parse_document(html);
+        let articles = Selector::parse("article").unwrap();
+        let examples = Selector::parse("pre > code").unwrap();
+
+        let mut markdown = String::new();
+        let mut puzzle_examples = Vec::new();
+        for article in document.select(&articles) {
+            render(article, &mut markdown);
+            markdown.push_str("\n\n");
+            puzzle_examples.extend(
+                article
+                    .select(&examples)
+                    .map(|code| code.text().collect::<String>().trim_end().to_string()),
+            );
+        }
+        Self {
+            markdown: tidy(&markdown),
+            examples: puzzle_examples,
+        }
+    }
+
+    /// Whether part two has been unlocked, so there is nothing more to download.
+    pub fn is_complete(&self) -> bool {
+        self.markdown.contains("--- Part Two ---")
+    }
+}
+
+fn children_markdown(element: ElementRef) -> String {
+    let mut out = String::new();
+    render(element, &mut out);
+    out
+}
+
+/// Appends the Markdown for the children of `element` to `out`. Only the handful of tags used
+/// on puzzle pages get special treatment.
+fn render(element: ElementRef, out: &mut String) {
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => out.push_str(text),
+            Node::Element(_) => {
+                let child = ElementRef::wrap(child).unwrap();
+                match child.value().name() {
+                    "h2" => {
+                        out.push_str(&format!("\n\n## {}\n\n", children_markdown(child).trim()))
+                    }
+                    "p" => out.push_str(&format!("\n\n{}\n\n", children_markdown(child).trim())),
+                    "pre" => out.push_str(&format!(
+                        "\n\n```\n{}\n```\n\n",
+                        child.text().collect::<String>().trim_end()
+                    )),
+                    "code" => out.push_str(&format!("`{}`", child.text().collect::<String>())),
+                    "em" => out.push_str(&format!("*{}*", children_markdown(child))),
+                    "ul" => {
+                        let items: String = child
+                            .children()
+                            .filter_map(ElementRef::wrap)
+                            .map(|li| format!("- {}\n", children_markdown(li).trim()))
+                            .collect();
+                        out.push_str(&format!("\n\n{items}\n"));
+                    }
+                    "a" => out.push_str(&format!(
+                        "[{}]({})",
+                        children_markdown(child),
+                        child.value().attr("href").unwrap_or_default()
+                    )),
+                    "br" => out.push('\n'),
+                    _ => render(child, out),
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Trims every line and collapses runs of blank lines, outside of code blocks.
+fn tidy(markdown: &str) -> String {
+    let mut out = String::new();
+    let mut in_code = false;
+    let mut blank = true;
+    for line in markdown.lines() {
+        if line.trim() == "```" {
+            in_code = !in_code;
+        }
+        let line = if in_code { line } else { line.trim() };
+        if line.is_empty() && !in_code {
+            if !blank {
+                out.push('\n');
+            }
+            blank = true;
+            continue;
+        }
+        out.push_str(line);
+        out.push('\n');
+        blank = false;
+    }
+    out.trim_end().to_string() + "\n"
+}
+
+/// Puzzle descriptions stored as `{dir}/{year}/day{day}.md`, with their examples beside them in
+/// `{dir}/{year}/day{day}/example{n}.txt`.
+#[derive(Debug, Clone)]
+pub struct PuzzleCache {
+    dir: PathBuf,
+}
+
+impl PuzzleCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The cache at [`PUZZLE_DIR_ENV`], or `puzzles`.
+    pub fn from_env() -> Self {
+        Self::new(env::var(PUZZLE_DIR_ENV).unwrap_or_else(|_| "puzzles".to_string()))
+    }
+
+    pub fn markdown_path(&self, year: u16, day: u8) -> PathBuf {
+        self.dir.join(year.to_string()).join(format!("day{day}.md"))
+    }
+
+    /// Where the `n`th example, counting from 1, is stored.
+    pub fn example_path(&self, year: u16, day: u8, n: usize) -> PathBuf {
+        self.dir
+            .join(year.to_string())
+            .join(format!("day{day}"))
+            .join(format!("example{n}.txt"))
+    }
+
+    pub fn load(&self, year: u16, day: u8) -> Option<Puzzle> {
+        let markdown = read_to_string(self.markdown_path(year, day)).ok()?;
+        let examples = (1..)
+            .map_while(|n| read_to_string(self.example_path(year, day, n)).ok())
+            .collect();
+        Some(Puzzle { markdown, examples })
+    }
+
+    pub fn store(&self, year: u16, day: u8, puzzle: &Puzzle) -> io::Result<()> {
+        write_atomic(&self.markdown_path(year, day), &puzzle.markdown)?;
+        for (i, example) in puzzle.examples.iter().enumerate() {
+            write_atomic(&self.example_path(year, day, i + 1), example)?;
+        }
+        Ok(())
+    }
+}
+
+impl Http {
+    /// Downloads the puzzle page, signed in if a session is available so part two is included.
+    pub async fn fetch_page(&self, year: u16, day: u8) -> Result<String, InputError> {
+        let url = format!("{}/{year}/day/{day}", self.base_url());
+        let mut request = reqwest::Client::new().get(url);
+        if let Ok(cookie) = self.cookie() {
+            request = request.header(COOKIE, cookie);
+        }
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(InputError::Http(response.status()));
+        }
+        Ok(response.text().await?)
+    }
+}
+
+/// Returns the cached puzzle if it is complete, and otherwise downloads it through `http` and
+/// caches it. Without `http`, whatever is cached is returned.
+pub async fn fetch_puzzle_with(
+    http: Option<&Http>,
+    cache: &PuzzleCache,
+    year: u16,
+    day: u8,
+) -> Result<Puzzle, InputError> {
+    let cached = cache.load(year, day);
+    let Some(http) = http.filter(|_| !cached.as_ref().is_some_and(Puzzle::is_complete)) else {
+        return cached.ok_or(InputError::Offline { year, day });
+    };
+    let puzzle = Puzzle::from_html(&http.fetch_page(year, day).await?);
+    if puzzle.markdown.trim().is_empty() {
+        return Err(InputError::EmptyBody);
+    }
+    cache.store(year, day, &puzzle)?;
+    Ok(puzzle)
+}
+
+/// [`fetch_puzzle_with`] adventofcode.com (or [`BASE_URL_ENV`]) and the default cache, staying
+/// offline if asked to.
+pub async fn fetch_puzzle(year: u16, day: u8) -> Result<Puzzle, InputError> {
+    let http = env::var(BASE_URL_ENV).map_or_else(|_| Http::default(), Http::new);
+    let http = (!offline()).then_some(&http);
+    fetch_puzzle_with(http, &PuzzleCache::from_env(), year, day).await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::mock_server::MockServer;
+
+    const PAGE: &str = include_str!("../../fixtures/puzzle.html");
+
+    #[test]
+    fn markdown() {
+        let puzzle = Puzzle::from_html(PAGE);
+        assert_eq!(
+            puzzle.markdown,
+            "## --- Day 1: Sample Sorting ---
+
+The elves have two lists of *location IDs* & want to compare them.
+
+For example:
+
+```
+3   4
+4   3
+2   5
+```
+
+Pair up the numbers and add up the distances:
+
+- The smallest pair is `1` and `3`.
+- See [the list docs](https://example.com/lists).
+
+In this example, the total is `11`.
+
+## --- Part Two ---
+
+Now count how often each number appears:
+
+```
+1 2
+3 4
+```
+
+The similarity score is *31*.
+"
+        );
+        assert_eq!(puzzle.examples, ["3   4\n4   3\n2   5", "1 2\n3 4"]);
+        assert!(puzzle.is_complete());
+    }
+
+    #[tokio::test]
+    async fn caches_pages() {
+        let server = MockServer::start(vec![("GET /2024/day/1", 200, PAGE)]).await;
+        let http = Http::new(server.url()).with_session("abc");
+        let dir = env::temp_dir().join(format!("aoc-puzzles-{}", std::process::id()));
+        let cache = PuzzleCache::new(&dir);
+
+        assert!(matches!(
+            fetch_puzzle_with(None, &cache, 2024, 1).await,
+            Err(InputError::Offline { .. })
+        ));
+        let puzzle = fetch_puzzle_with(Some(&http), &cache, 2024, 1)
+            .await
+            .unwrap();
+        assert_eq!(cache.load(2024, 1), Some(puzzle.clone()));
+        assert_eq!(
+            read_to_string(cache.example_path(2024, 1, 2)).unwrap(),
+            "1 2\n3 4"
+        );
+        // Complete puzzles come from the cache.
+        fetch_puzzle_with(Some(&http), &cache, 2024, 1)
+            .await
+            .unwrap();
+        assert_eq!(server.requests().len(), 1);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,256 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    fs::{self, read_to_string},
+    io,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+
+use super::input::write_atomic;
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    Exists(PathBuf),
+    /// `lib.rs` or the year's `mod.rs` doesn't have the layout new modules are registered into.
+    Registry(PathBuf),
+    Io(io::Error),
+}
+
+impl Display for ScaffoldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exists(path) => write!(f, "{} already exists", path.display()),
+            Self::Registry(path) => {
+                write!(
+                    f,
+                    "couldn't find where to register the day in {}",
+                    path.display()
+                )
+            }
+            Self::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for ScaffoldError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ScaffoldError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+/// The module directory for `year` under `root`, like `src/aoc24`.
+pub fn year_dir(root: &Path, year: u16) -> PathBuf {
+    root.join("src").join(format!("aoc{:02}", year % 100))
+}
+
+/// A new day module with stub parts and `example` (if any) wired into its example tests.
+pub fn render_day(year: u16, day: u8, example: Option<&str>) -> String {
+    let example = example.unwrap_or_default().trim_end();
+    // One more `#` than follows any quote in the example, so it can't close the raw string.
+    let hashes = example
+        .split('"')
+        .skip(1)
+        .map(|after| after.len() - after.trim_start_matches('#').len() + 1)
+        .max()
+        .unwrap_or(0);
+    let quote = "#".repeat(hashes);
+    format!(
+        r#"use crate::{{
+    solution::{{Example, Solution}},
+    util::{{Answer, ParseError}},
+}};
+
+const EXAMPLE_INPUT: &str = r{quote}"{example}"{quote};
+
+pub struct Day{day:02};
+
+impl Solution for Day{day:02} {{
+    const YEAR: u16 = {year};
+    const DAY: u8 = {day};
+
+    type Config = ();
+    type Input = Vec<String>;
+
+    fn parse(input: &str, _: &()) -> Result<Self::Input, ParseError> {{
+        Ok(input.lines().map(str::to_owned).collect())
+    }}
+
+    fn part1(_input: &Self::Input) -> Answer {{
+        todo!()
+    }}
+
+    fn part2(_input: &Self::Input) -> Answer {{
+        todo!()
+    }}
+
+    fn examples() -> Vec<Example> {{
+        vec![Example::new(EXAMPLE_INPUT)]
+    }}
+}}
+"#
+    )
+}
+
+/// Inserts `new` after the last line matched by `pattern` whose captured day sorts before `day`,
+/// or before the first matching line if there is none.
+fn insert_sorted(source: &str, pattern: &Regex, day: u8, new: &str) -> Option<String> {
+    let mut lines: Vec<&str> = source.lines().collect();
+    let matches: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| Some((i, pattern.captures(l)?[1].parse().ok()?)))
+        .collect();
+    let first = matches.first()?.0;
+    let at = matches
+        .iter()
+        .rev()
+        .find(|&&(_, d)| d < day)
+        .map_or(first, |&(i, _)| i + 1);
+    lines.insert(at, new);
+    Some(lines.join("\n") + "\n")
+}
+
+/// The `mod.rs` of a new year whose only day is `day`.
+pub fn render_year(year: u16, day: u8) -> String {
+    format!(
+        "use crate::solution::solutions;
+
+pub mod day{day:02};
+
+solutions!(
+    {year};
+    day{day:02} => Day{day:02},
+);
+"
+    )
+}
+
+/// Adds the module for `year` to the `years!` invocation in `lib.rs`.
+pub fn register_year(lib_rs: &str, year: u16) -> Option<String> {
+    let years = Regex::new(r"^\s+aoc(\d+),$").unwrap();
+    let yy = (year % 100) as u8;
+    insert_sorted(lib_rs, &years, yy, &format!("    aoc{yy:02},"))
+}
+
+/// Adds `day` to the `pub mod` list and the `solutions!` invocation of a year's `mod.rs`.
+pub fn register_day(mod_rs: &str, day: u8) -> Option<String> {
+    let modules = Regex::new(r"^pub mod day(\d+);$").unwrap();
+    let solutions = Regex::new(r"^\s+day(\d+) => Day\d+,$").unwrap();
+    let mod_rs = insert_sorted(mod_rs, &modules, day, &format!("pub mod day{day:02};"))?;
+    insert_sorted(
+        &mod_rs,
+        &solutions,
+        day,
+        &format!("    day{day:02} => Day{day:02},"),
+    )
+}
+
+/// Writes the module for `day` of `year` in the crate at `root` and registers it, along with a
+/// module for the year if it is the first day of it. Returns the new day's path.
+pub fn scaffold_day(
+    root: &Path,
+    year: u16,
+    day: u8,
+    example: Option<&str>,
+) -> Result<PathBuf, ScaffoldError> {
+    let dir = year_dir(root, year);
+    let mod_path = dir.join("mod.rs");
+    let path = dir.join(format!("day{day:02}.rs"));
+    if path.exists() {
+        return Err(ScaffoldError::Exists(path));
+    }
+    if !mod_path.exists() {
+        let lib_path = root.join("src").join("lib.rs");
+        let registered = register_year(&read_to_string(&lib_path)?, year)
+            .ok_or(ScaffoldError::Registry(lib_path.clone()))?;
+        fs::create_dir_all(&dir)?;
+        fs::write(&path, render_day(year, day, example))?;
+        fs::write(&mod_path, render_year(year, day))?;
+        write_atomic(&lib_path, &registered)?;
+        return Ok(path);
+    }
+    let registered = register_day(&read_to_string(&mod_path)?, day)
+        .ok_or(ScaffoldError::Registry(mod_path.clone()))?;
+    fs::write(&path, render_day(year, day, example))?;
+    write_atomic(&mod_path, &registered)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn registers_in_order() {
+        let mod_rs = "use crate::solution::solutions;
+
+pub mod day01;
+pub mod day03;
+
+solutions!(
+    2024;
+    day01 => Day01,
+    day03 => Day03,
+);
+";
+        assert_eq!(
+            register_day(mod_rs, 2).unwrap(),
+            "use crate::solution::solutions;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+
+solutions!(
+    2024;
+    day01 => Day01,
+    day02 => Day02,
+    day03 => Day03,
+);
+"
+        );
+        assert!(register_day(mod_rs, 4)
+            .unwrap()
+            .contains("pub mod day03;\npub mod day04;\n"));
+        assert!(register_day("fn main() {}\n", 4).is_none());
+    }
+
+    #[test]
+    fn checked_in_registry_layout() {
+        let mod_rs = read_to_string("src/aoc24/mod.rs").unwrap();
+        let removed = mod_rs
+            .replace("pub mod day25;\n", "")
+            .replace("    day25 => Day25,\n", "");
+        assert_eq!(register_day(&removed, 25).unwrap(), mod_rs);
+    }
+
+    #[test]
+    fn registers_years() {
+        let lib_rs = read_to_string("src/lib.rs").unwrap();
+        let registered = register_year(&lib_rs, 2023).unwrap();
+        assert!(registered.contains("years! {\n    aoc23,\n    aoc24,\n}"));
+        assert!(register_year(&registered, 2025)
+            .unwrap()
+            .contains("    aoc24,\n    aoc25,\n}"));
+    }
+
+    #[test]
+    fn example_quoting() {
+        assert!(
+            render_day(2024, 5, Some("a\nb\n")).contains("const EXAMPLE_INPUT: &str = r\"a\nb\";")
+        );
+        assert!(render_day(2024, 5, Some("say \"hi\"")).contains("r#\"say \"hi\"\"#;"));
+        assert!(render_day(2024, 5, Some("a\"#b\"##")).contains("r###\"a\"#b\"##\"###;"));
+    }
+}
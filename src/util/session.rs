@@ -0,0 +1,61 @@
+use std::{env, fs::read_to_string, path::PathBuf, sync::OnceLock};
+
+use super::input::InputError;
+
+/// Environment variable holding the adventofcode.com session token.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+static SESSION_OVERRIDE: OnceLock<String> = OnceLock::new();
+
+/// Uses `token` for every request made by this process, ahead of the environment and config
+/// file. Only the first call has any effect.
+pub fn set_session(token: impl Into<String>) {
+    let _ = SESSION_OVERRIDE.set(token.into());
+}
+
+/// Where the session token is read from when neither the override nor [`SESSION_ENV`] is set.
+pub fn session_file() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("aoc").join("session"))
+}
+
+/// Turns a raw token or a full `session=...` cookie into the cookie header value.
+pub(crate) fn to_cookie(raw: &str) -> Option<String> {
+    let token = raw.trim();
+    let token = token.strip_prefix("session=").unwrap_or(token).trim();
+    (!token.is_empty()).then(|| format!("session={token}"))
+}
+
+/// Resolves the session cookie from, in order, [`set_session`], [`SESSION_ENV`] and
+/// [`session_file`].
+pub fn session_cookie() -> Result<String, InputError> {
+    SESSION_OVERRIDE
+        .get()
+        .and_then(|token| to_cookie(token))
+        .or_else(|| {
+            env::var(SESSION_ENV)
+                .ok()
+                .and_then(|token| to_cookie(&token))
+        })
+        .or_else(|| {
+            session_file()
+                .and_then(|path| read_to_string(path).ok())
+                .and_then(|token| to_cookie(&token))
+        })
+        .ok_or(InputError::MissingCookie)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cookie_formats() {
+        assert_eq!(to_cookie("abc123\n").as_deref(), Some("session=abc123"));
+        assert_eq!(
+            to_cookie("session=abc123").as_deref(),
+            Some("session=abc123")
+        );
+        assert_eq!(to_cookie("  \n"), None);
+        assert_eq!(to_cookie("session="), None);
+    }
+}
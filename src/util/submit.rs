@@ -1,129 +1,297 @@
-use std::{
-    collections::HashMap,
-    fs::{read_to_string, File},
-    io::{BufWriter, Write},
-    sync::LazyLock,
-};
+use std::{error::Error, fmt::Display, io, sync::LazyLock, time::Duration};
 
+use regex::Regex;
 use reqwest::header::COOKIE;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    answer::Answer,
+    answers::AnswerStore,
+    input::{offline, Http, InputError, BASE_URL_ENV},
+    ledger::{Attempt, Ledger, Rejection},
+};
 
-const MY_COOKIE: &str = include_str!("../../cookie.txt");
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// What the site said about a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SubmitOutcome {
+    Correct,
+    Incorrect(Option<Hint>),
+    RateLimited(Duration),
+    AlreadySolved,
+}
 
-pub async fn fetch_input(year: u16, day: u8) -> String {
-    let fname = format!("inputs/{}/day{}.txt", year, day);
-    if let Ok(contents) = read_to_string(&fname) {
-        return contents;
+impl Display for SubmitOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::Incorrect(None) => write!(f, "incorrect"),
+            Self::Incorrect(Some(Hint::TooHigh)) => write!(f, "incorrect (too high)"),
+            Self::Incorrect(Some(Hint::TooLow)) => write!(f, "incorrect (too low)"),
+            Self::RateLimited(wait) => write!(f, "rate limited, wait {}s", wait.as_secs()),
+            Self::AlreadySolved => write!(f, "already solved"),
+        }
     }
-    let url = format!("https://adventofcode.com/{}/day/{}/input", year, day);
-    let data = reqwest::Client::new()
-        .get(url)
-        .header(COOKIE, MY_COOKIE)
-        .send()
-        .await
-        .unwrap()
-        .text()
-        .await
-        .unwrap();
-    let f = File::options()
-        .create(true)
-        .write(true)
-        .open(&fname)
-        .unwrap();
-    let mut buf = BufWriter::new(f);
-    write!(&mut buf, "{}", data).unwrap();
-    data
 }
 
-static CORRECT_ANSWERS: LazyLock<HashMap<(u8, u64), u64>> = LazyLock::new(|| {
-    [
-        ((1, 1), 1590491),
-        ((1, 2), 22588371),
-        ((2, 1), 516),
-        ((2, 2), 561),
-        ((3, 1), 174561379),
-        ((3, 2), 106921067),
-        ((4, 1), 2549),
-        ((4, 2), 2003),
-        ((5, 1), 4578),
-        ((5, 2), 6179),
-        ((6, 1), 5305),
-        ((6, 2), 2143),
-        ((7, 1), 1620690235709),
-        ((7, 2), 145397611075341),
-        ((8, 1), 413),
-        ((8, 2), 1417),
-        ((9, 1), 6607511583593),
-        ((9, 2), 6636608781232),
-        ((10, 1), 825),
-        ((10, 2), 1805),
-        ((11, 1), 218956),
-        ((11, 2), 259593838049805),
-        ((12, 1), 1396298),
-        ((12, 2), 853588),
-        ((13, 1), 29023),
-        ((13, 2), 96787395375634),
-        ((14, 1), 230686500),
-        ((14, 2), 7672),
-        ((15, 1), 1446158),
-        ((15, 2), 1446175),
-        ((16, 1), 107468),
-        ((16, 2), 533),
-        ((17, 2), 190593310997519),
-        ((18, 1), 314),
-        ((19, 1), 233),
-        ((19, 2), 691316989225259),
-        ((20, 1), 1367),
-        ((20, 2), 1006850),
-        ((21, 1), 123096),
-        ((21, 2), 154517692795352),
-        ((22, 1), 20411980517),
-        ((22, 2), 2362),
-        ((23, 1), 1046),
-        ((24, 1), 69201640933606),
-        ((25, 1), 3155),
-        ((25, 2), 0), // compatibility
-    ]
-    .into_iter()
-    .collect()
-});
-
-static CORRECT_STR_ANSWERS: LazyLock<HashMap<(u8, u64), &str>> = LazyLock::new(|| {
-    [
-        ((17, 1), "3,1,5,3,7,4,2,7,5"),
-        ((18, 2), "15,20"),
-        ((23, 2), "de,id,ke,ls,po,sn,tf,tl,tm,uj,un,xw,yz"),
-        ((24, 2), "dhq,hbs,jcp,kfp,pdg,z18,z22,z27"),
-    ]
-    .into_iter()
-    .collect()
-});
+#[derive(Debug)]
+pub enum SubmitError {
+    Offline,
+    Rejected(Rejection),
+    Request(InputError),
+    UnrecognizedResponse(String),
+    Ledger(io::Error),
+}
 
-#[cfg(test)]
-pub fn submit_int(_year: u16, day: u8, level: u64, answer: u64) -> String {
-    if let Some(&val) = CORRECT_ANSWERS.get(&(day, level)) {
-        if answer == val {
-            return format!("right ({answer})");
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Offline => write!(f, "submissions are disabled in offline mode"),
+            Self::Rejected(r) => write!(f, "not submitted, {r}"),
+            Self::Request(e) => write!(f, "{e}"),
+            Self::UnrecognizedResponse(_) => write!(f, "couldn't make sense of the response"),
+            Self::Ledger(e) => write!(f, "couldn't record the submission: {e}"),
         }
-        return format!("wrong (expected {val}, received {answer})");
     }
-    return "unsolved".to_owned();
 }
 
-#[cfg(test)]
-pub fn submit_str(_year: u16, day: u8, level: u64, answer: String) -> String {
-    if let Some(&val) = CORRECT_STR_ANSWERS.get(&(day, level)) {
-        if answer == val {
-            return format!("right ({answer})");
+impl Error for SubmitError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Request(e) => Some(e),
+            Self::Ledger(e) => Some(e),
+            _ => None,
         }
-        return format!("wrong (expected {val}, received {answer})");
     }
-    return "unsolved".to_owned();
+}
+
+impl From<InputError> for SubmitError {
+    fn from(value: InputError) -> Self {
+        Self::Request(value)
+    }
+}
+
+impl From<reqwest::Error> for SubmitError {
+    fn from(value: reqwest::Error) -> Self {
+        Self::Request(value.into())
+    }
+}
+
+/// Reads a wait like `1m 5s` out of a rate limit message.
+fn parse_wait(text: &str) -> Option<Duration> {
+    static WAIT: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"have (?:(\d+)m ?)?(?:(\d+)s )?left to wait").unwrap());
+    let caps = WAIT.captures(text)?;
+    let field = |i| caps.get(i).map_or(0, |m| m.as_str().parse().unwrap_or(0));
+    Some(Duration::from_secs(field(1) * 60 + field(2)))
+}
+
+/// Interprets the page returned after posting an answer.
+pub fn parse_response(html: &str) -> Option<SubmitOutcome> {
+    if html.contains("That's the right answer") {
+        Some(SubmitOutcome::Correct)
+    } else if html.contains("That's not the right answer") {
+        let hint = if html.contains("your answer is too high") {
+            Some(Hint::TooHigh)
+        } else if html.contains("your answer is too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        Some(SubmitOutcome::Incorrect(hint))
+    } else if html.contains("You gave an answer too recently") {
+        Some(SubmitOutcome::RateLimited(
+            parse_wait(html).unwrap_or(Duration::from_secs(60)),
+        ))
+    } else if html.contains("Did you already complete it") {
+        Some(SubmitOutcome::AlreadySolved)
+    } else {
+        None
+    }
+}
+
+impl Http {
+    /// Posts `answer` for `part` of `day` and interprets the reply.
+    pub async fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &Answer,
+    ) -> Result<SubmitOutcome, SubmitError> {
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url());
+        let response = reqwest::Client::new()
+            .post(url)
+            .header(COOKIE, self.cookie()?)
+            .form(&[("level", part.to_string()), ("answer", answer.to_string())])
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(InputError::Http(response.status()).into());
+        }
+        let html = response.text().await?;
+        parse_response(&html).ok_or(SubmitError::UnrecognizedResponse(html))
+    }
+}
+
+/// Everything a submission touches: the site, the ledger of attempts and the known answers.
+#[derive(Debug, Clone)]
+pub struct Submitter {
+    pub http: Http,
+    pub ledger: Ledger,
+    pub answers: AnswerStore,
+}
+
+impl Submitter {
+    /// Submits to adventofcode.com (or [`BASE_URL_ENV`]) using the default ledger and answers.
+    pub fn from_env() -> Self {
+        Self {
+            http: std::env::var(BASE_URL_ENV).map_or_else(|_| Http::default(), Http::new),
+            ledger: Ledger::from_env(),
+            answers: AnswerStore::from_env(),
+        }
+    }
+
+    /// Submits `answer` unless earlier attempts already rule it out, records the attempt, and
+    /// stores the answer as known once the site accepts it.
+    pub async fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: Answer,
+    ) -> Result<SubmitOutcome, SubmitError> {
+        self.ledger
+            .bounds(year, day, part)
+            .map_err(SubmitError::Ledger)?
+            .check(&answer)
+            .map_err(SubmitError::Rejected)?;
+        let outcome = self.http.submit(year, day, part, &answer).await?;
+        self.ledger
+            .record(&Attempt::now(year, day, part, answer.clone(), outcome))
+            .map_err(SubmitError::Ledger)?;
+        if outcome == SubmitOutcome::Correct {
+            self.answers
+                .record(year, day, part, answer)
+                .map_err(SubmitError::Ledger)?;
+        }
+        Ok(outcome)
+    }
+}
+
+/// Submits `answer` with [`Submitter::from_env`].
+pub async fn submit(
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: Answer,
+) -> Result<SubmitOutcome, SubmitError> {
+    if offline() {
+        return Err(SubmitError::Offline);
+    }
+    Submitter::from_env().submit(year, day, part, answer).await
 }
 
 #[cfg(test)]
-pub fn submit(year: u16, day: u8, level: u64, answer: String) -> String {
-    if CORRECT_STR_ANSWERS.contains_key(&(day, level)) {
-        return submit_str(year, day, level, answer);
+mod test {
+    use super::*;
+    use crate::util::{mock_server::MockServer, Expected};
+
+    #[test]
+    fn responses() {
+        let page = |msg: &str| format!("<main>\n<article><p>{msg}</p></article>\n</main>");
+        assert_eq!(
+            parse_response(&page(
+                "That's the right answer!  You are one gold star closer."
+            )),
+            Some(SubmitOutcome::Correct)
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer; your answer is too high.  Please wait one minute."
+            )),
+            Some(SubmitOutcome::Incorrect(Some(Hint::TooHigh)))
+        );
+        assert_eq!(
+            parse_response(&page("That's not the right answer.  If you're stuck...")),
+            Some(SubmitOutcome::Incorrect(None))
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait."
+            )),
+            Some(SubmitOutcome::RateLimited(Duration::from_secs(65)))
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You gave an answer too recently.  You have 34s left to wait."
+            )),
+            Some(SubmitOutcome::RateLimited(Duration::from_secs(34)))
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Some(SubmitOutcome::AlreadySolved)
+        );
+        assert_eq!(parse_response(&page("Something else")), None);
+    }
+
+    #[tokio::test]
+    async fn submit_records_attempts() {
+        let server = MockServer::start(vec![
+            (
+                "POST /2024/day/3/answer",
+                200,
+                "<article><p>That's not the right answer; your answer is too low.</p></article>",
+            ),
+            (
+                "POST /2024/day/5/answer",
+                200,
+                "<article><p>That's the right answer!</p></article>",
+            ),
+        ])
+        .await;
+        let dir = std::env::temp_dir().join(format!("aoc-submit-{}", std::process::id()));
+        let submitter = Submitter {
+            http: Http::new(server.url()).with_session("abc"),
+            ledger: Ledger::new(dir.join("ledger.jsonl")),
+            answers: AnswerStore::new(&dir),
+        };
+
+        let outcome = submitter.submit(2024, 3, 2, 42u64.into()).await.unwrap();
+        assert_eq!(outcome, SubmitOutcome::Incorrect(Some(Hint::TooLow)));
+        let request = &server.requests()[0];
+        assert!(request.contains("cookie: session=abc"));
+        assert!(request.ends_with("level=2&answer=42"));
+
+        let attempts = submitter.ledger.attempts().unwrap();
+        assert_eq!(attempts.len(), 1);
+        assert_eq!((attempts[0].day, attempts[0].part), (3, 2));
+        assert_eq!(attempts[0].outcome, outcome);
+
+        assert!(matches!(
+            submitter.submit(2024, 3, 2, 41u64.into()).await,
+            Err(SubmitError::Rejected(Rejection::TooLow { bound: 42 }))
+        ));
+        assert_eq!(server.requests().len(), 1);
+
+        assert!(matches!(
+            submitter.submit(2024, 4, 1, 1u64.into()).await,
+            Err(SubmitError::Request(InputError::Http(_)))
+        ));
+
+        let outcome = submitter.submit(2024, 5, 1, 9u64.into()).await.unwrap();
+        assert_eq!(outcome, SubmitOutcome::Correct);
+        assert_eq!(
+            submitter.answers.load(2024).unwrap().expected(5, 1),
+            Expected::Answer(Answer::Int(9))
+        );
+        std::fs::remove_dir_all(dir).unwrap();
     }
-    submit_int(year, day, level, answer.parse::<u64>().unwrap())
 }
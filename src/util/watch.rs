@@ -0,0 +1,63 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// What a file looked like when it was last polled, `None` if it didn't exist.
+type Stamp = Option<(SystemTime, u64)>;
+
+fn stamp(path: &Path) -> Stamp {
+    let meta = fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
+/// Polls a set of files for changes by their modification time and size.
+#[derive(Debug, Clone, Default)]
+pub struct Watcher {
+    seen: Vec<(PathBuf, Stamp)>,
+}
+
+impl Watcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether any of `files` was created, changed or removed since the last poll, or the set of
+    /// files itself changed. The first poll always reports a change.
+    pub fn poll(&mut self, files: &[PathBuf]) -> bool {
+        let seen = files
+            .iter()
+            .map(|f| (f.clone(), stamp(f)))
+            .collect::<Vec<_>>();
+        let changed = self.seen.is_empty() || seen != self.seen;
+        self.seen = seen;
+        changed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn detects_changes() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("day1.txt");
+        let files = [file.clone()];
+        let mut watcher = Watcher::new();
+
+        assert!(watcher.poll(&files));
+        assert!(!watcher.poll(&files));
+        fs::write(&file, "1").unwrap();
+        assert!(watcher.poll(&files));
+        assert!(!watcher.poll(&files));
+        fs::write(&file, "12").unwrap();
+        assert!(watcher.poll(&files));
+        fs::remove_file(&file).unwrap();
+        assert!(watcher.poll(&files));
+        assert!(watcher.poll(&[file, dir.join("example1.txt")]));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}